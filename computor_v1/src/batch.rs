@@ -0,0 +1,140 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+
+use computor_v1::{reduce, solve, make_root_string, make_solution_json, make_error_json,
+    make_reduced_form_latex, make_solution_latex, Solution};
+
+use crate::{Format, Output};
+
+
+fn with_line(line_number: usize, json: &str) -> String {
+    // The line number is added as the first member of the object
+    format!("{{\"line\":{},{}", line_number, &json[1..])
+}
+
+
+fn text_solution(solution: &Solution) -> String {
+    match solution {
+        Solution::NoSolution => "no solution".to_string(),
+        Solution::AllReals => "any real number".to_string(),
+        Solution::Unsolvable => "unsolvable".to_string(),
+        Solution::Roots {roots, ..} => {
+            let roots_string: Vec<String> = roots.iter().map(make_root_string).collect();
+            format!("solutions {}", roots_string.join(", "))
+        },
+    }
+}
+
+
+fn record(output: Output, line_number: usize, equation: &str) -> Result<String, String> {
+    // A line that cannot be parsed gives its error record as Err
+    let polynomial = match reduce(equation) {
+        Ok(v) => v,
+        Err(e) => return Err(match output.format {
+            Format::Json => with_line(line_number, &make_error_json(&e)),
+            _ => {
+                let column = equation[..e.span.start.min(equation.len())].chars().count() + 1;
+                format!("{}: error: {} at column {}", line_number, e, column)
+            },
+        }),
+    };
+    let solution = solve(&polynomial);
+    Ok(match output.format {
+        Format::Json => with_line(line_number, &make_solution_json(&polynomial, &solution)),
+        Format::Latex => format!("{}: {} \\quad {}", line_number, make_reduced_form_latex(polynomial.terms(), polynomial.variable()),
+            make_solution_latex(&polynomial, &solution).replace('\n', " \\quad ")),
+        Format::Text | Format::Steps => {
+            let record = format!("{}: {}; degree {}; {}", line_number, output.reduced_form(&polynomial),
+                polynomial.degree(), text_solution(&solution));
+            if polynomial.excludes_zero() {
+                format!("{}; {} = 0 excluded", record, polynomial.variable())
+            } else {
+                record
+            }
+        },
+    })
+}
+
+
+pub fn run(output: Output, path: Option<&String>) -> io::Result<usize> {
+    // Returns the number of lines that could not be parsed
+    let mut reader: Box<dyn BufRead> = match path.map(|s| s.as_str()) {
+        None | Some("-") => Box::new(BufReader::new(io::stdin())),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut bytes = Vec::new();
+    let mut line_number = 0;
+    let mut error_count = 0;
+    loop {
+        bytes.clear();
+        if reader.read_until(b'\n', &mut bytes)? == 0 {
+            return Ok(error_count)
+        }
+        line_number += 1;
+        // Invalid UTF-8 is reported by the parser instead of stopping the batch
+        let line = String::from_utf8_lossy(&bytes);
+        let equation = line.trim_end_matches(&['\n', '\r'][..]);
+        if equation.trim().is_empty() {
+            continue
+        }
+        match record(output, line_number, equation) {
+            Ok(record) => writeln!(out, "{}", record)?,
+            Err(record) => {
+                error_count += 1;
+                writeln!(out, "{}", record)?;
+            },
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Notation;
+
+    fn output(format: Format) -> Output {
+        Output {format, notation: Notation::Standard}
+    }
+
+    #[test]
+    fn record_text() {
+        assert_eq!(record(output(Format::Text), 3, "X^2 = 4"),
+            Ok("3: - 4 * X^0 + 1 * X^2 = 0; degree 2; solutions 2, -2".to_string()));
+    }
+
+    #[test]
+    fn record_text_error() {
+        assert_eq!(record(output(Format::Text), 7, "X + * 2 = 0"), Err("7: error: Misplaced operator at column 5".to_string()));
+    }
+
+    #[test]
+    fn record_json_error() {
+        assert_eq!(record(output(Format::Json), 2, "X = = 1"),
+            Err("{\"line\":2,\"error\":{\"message\":\"There were multiple equals\",\"start\":4,\"end\":5}}".to_string()));
+    }
+
+    #[test]
+    fn record_latex() {
+        assert_eq!(record(output(Format::Latex), 1, "X = 1"), Ok("1: - 1 + x = 0 \\quad x = 1".to_string()));
+    }
+
+    #[test]
+    fn record_text_all_reals() {
+        assert_eq!(record(output(Format::Text), 1, "X = X"), Ok("1: 0 * X^1 = 0; degree 0; any real number".to_string()));
+    }
+
+    #[test]
+    fn record_text_excluded_zero() {
+        assert_eq!(record(output(Format::Text), 4, "1 + X^-1 = 0"),
+            Ok("4: 1 * X^0 + 1 * X^1 = 0; degree 1; solutions -1; X = 0 excluded".to_string()));
+    }
+
+    #[test]
+    fn record_text_natural() {
+        let output = Output {format: Format::Text, notation: Notation::Natural};
+        assert_eq!(record(output, 1, "X^2 = 4"), Ok("1: X^2 - 4 = 0; degree 2; solutions 2, -2".to_string()));
+    }
+}
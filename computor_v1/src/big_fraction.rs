@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::big_int::BigInt;
+use crate::fraction::Fraction;
+
+
+// A fraction whose parts do not fit in i64, always irreducible with a positive bottom
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BigFraction {
+    top: BigInt,
+    bottom: BigInt,
+}
+
+
+fn power(n: i64, exponent: u32) -> BigInt {
+    let mut value = BigInt::from_i64(1);
+    for _ in 0..exponent {
+        value = value.mul(&BigInt::from_i64(n));
+    }
+    value
+}
+
+
+impl BigFraction {
+    pub fn new(top: BigInt, bottom: BigInt) -> Option<BigFraction> {
+        if bottom.is_zero() {
+            return None
+        }
+        let gcd = top.gcd(&bottom);
+        let (mut top, _) = top.div_rem(&gcd)?;
+        let (mut bottom, _) = bottom.div_rem(&gcd)?;
+        if bottom.is_negative() {
+            top = top.neg();
+            bottom = bottom.neg();
+        }
+        Some(BigFraction {top, bottom})
+    }
+
+    pub fn from_fraction(fraction: &Fraction) -> BigFraction {
+        BigFraction {top: BigInt::from_i64(fraction.top()), bottom: BigInt::from_i64(fraction.bottom())}
+    }
+
+    pub fn from_big_int(n: BigInt) -> BigFraction {
+        BigFraction {top: n, bottom: BigInt::from_i64(1)}
+    }
+
+    pub fn top(&self) -> &BigInt {
+        &self.top
+    }
+
+    pub fn bottom(&self) -> &BigInt {
+        &self.bottom
+    }
+
+    pub fn to_fraction(&self) -> Option<Fraction> {
+        Fraction::safe_new(self.top.to_i64()?, self.bottom.to_i64()?)
+    }
+
+    pub fn add(&self, other: &BigFraction) -> BigFraction {
+        let top = self.top.mul(&other.bottom).add(&other.top.mul(&self.bottom));
+        BigFraction::new(top, self.bottom.mul(&other.bottom)).unwrap()
+    }
+
+    pub fn mul(&self, other: &BigFraction) -> BigFraction {
+        BigFraction::new(self.top.mul(&other.top), self.bottom.mul(&other.bottom)).unwrap()
+    }
+
+    pub fn div(&self, other: &BigFraction) -> Option<BigFraction> {
+        // None when dividing by zero
+        BigFraction::new(self.top.mul(&other.bottom), self.bottom.mul(&other.top))
+    }
+
+    pub fn neg(&self) -> BigFraction {
+        BigFraction {top: self.top.neg(), bottom: self.bottom.clone()}
+    }
+
+    pub fn to_float(&self) -> f64 {
+        // Both parts are scaled down first, so that a quotient in range is found even when they are not
+        let (top, top_scale) = self.top.to_scaled_float();
+        let (bottom, bottom_scale) = self.bottom.to_scaled_float();
+        let mut value = top / bottom;
+        let scale = top_scale - bottom_scale;
+        for _ in 0..scale.abs() {
+            if value == 0.0 || !value.is_finite() {
+                break
+            }
+            value = if scale > 0 {value * 4294967296.0} else {value / 4294967296.0};
+        }
+        value
+    }
+
+    pub fn to_decimal_string(&self) -> Option<String> {
+        // Only a bottom of the form 2^a * 5^b gives a finite decimal expansion
+        let mut bottom = self.bottom.clone();
+        let mut counts = [0_u32; 2];
+        for (i, factor) in [2, 5].into_iter().enumerate() {
+            loop {
+                let (quotient, rem) = bottom.div_rem(&BigInt::from_i64(factor))?;
+                if !rem.is_zero() {
+                    break
+                }
+                bottom = quotient;
+                counts[i] += 1;
+            }
+        }
+        if bottom != BigInt::from_i64(1) {
+            return None
+        }
+        let places = counts[0].max(counts[1]) as usize;
+        let scale = power(2, places as u32 - counts[0]).mul(&power(5, places as u32 - counts[1]));
+        let digits = format!("{}", self.top.abs().mul(&scale));
+        let digits = format!("{}{}", "0".repeat((places + 1).saturating_sub(digits.len())), digits);
+        let sign = if self.top.is_negative() {"-"} else {""};
+        let (int_part, decimal_part) = digits.split_at(digits.len() - places);
+        if places == 0 {
+            Some(format!("{}{}", sign, int_part))
+        } else {
+            Some(format!("{}{}.{}", sign, int_part, decimal_part))
+        }
+    }
+}
+
+
+impl fmt::Display for BigFraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.bottom == BigInt::from_i64(1) {
+            write!(f, "{}", self.top)
+        } else {
+            write!(f, "{} / {}", self.top, self.bottom)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big_fraction(top: &str, bottom: &str) -> BigFraction {
+        BigFraction::new(BigInt::parse(top).unwrap(), BigInt::parse(bottom).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn new_irreducible() {
+        let fraction = big_fraction("36893488147419103232", "-24");
+        assert_eq!(format!("{}", fraction), "-4611686018427387904 / 3".to_string());
+    }
+
+    #[test]
+    fn new_bottom_zero() {
+        assert_eq!(BigFraction::new(BigInt::from_i64(1), BigInt::from_i64(0)), None);
+    }
+
+    #[test]
+    fn add_back_to_fraction() {
+        let lhs = big_fraction("1", "36893488147419103232");
+        let rhs = big_fraction("-1", "36893488147419103232");
+        assert_eq!(lhs.add(&rhs).to_fraction(), Fraction::safe_new(0, 1));
+    }
+
+    #[test]
+    fn div_zero() {
+        assert_eq!(big_fraction("1", "3").div(&big_fraction("0", "1")), None);
+    }
+
+    #[test]
+    fn to_float_beyond_f64() {
+        let top = format!("1{}", "0".repeat(400));
+        let bottom = format!("3{}", "0".repeat(400));
+        assert!((big_fraction(&top, &bottom).to_float() * 3.0 - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn to_decimal_string_long() {
+        let fraction = big_fraction("-12345678901234567890123", "1000000000000000000000000");
+        assert_eq!(fraction.to_decimal_string(), Some("-0.012345678901234567890123".to_string()));
+    }
+
+    #[test]
+    fn to_decimal_string_repeating() {
+        assert_eq!(big_fraction("1", "300000000000000000000").to_decimal_string(), None);
+    }
+}
@@ -0,0 +1,500 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BigInt {
+    is_negative: bool,
+    // Little endian base 2^32 digits without leading zeros, zero has no digits
+    digits: Vec<u32>,
+}
+
+
+fn trim(digits: &mut Vec<u32>) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+}
+
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    if a.len() != b.len() {
+        return a.len().cmp(&b.len())
+    }
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        if x != y {
+            return x.cmp(y)
+        }
+    }
+    Ordering::Equal
+}
+
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut vec = Vec::new();
+    let mut carry = 0_u64;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        vec.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        vec.push(carry as u32);
+    }
+    vec
+}
+
+
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    // a must not be smaller than b
+    let mut vec = Vec::new();
+    let mut borrow = 0_i64;
+    for (i, x) in a.iter().enumerate() {
+        let mut diff = *x as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if diff < 0 {
+            diff += 1 << 32;
+            borrow = 1;
+        }
+        vec.push(diff as u32);
+    }
+    trim(&mut vec);
+    vec
+}
+
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut vec = vec![0_u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, y) in b.iter().enumerate() {
+            let value = vec[i + j] as u64 + *x as u64 * *y as u64 + carry;
+            vec[i + j] = value as u32;
+            carry = value >> 32;
+        }
+        vec[i + b.len()] = carry as u32;
+    }
+    trim(&mut vec);
+    vec
+}
+
+
+fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut vec = vec![0_u32; a.len()];
+    let mut rem = 0_u64;
+    for i in (0..a.len()).rev() {
+        let value = (rem << 32) | a[i] as u64;
+        vec[i] = (value / divisor as u64) as u32;
+        rem = value % divisor as u64;
+    }
+    trim(&mut vec);
+    (vec, rem as u32)
+}
+
+
+fn remove_trailing_zeros(a: &mut Vec<u32>) {
+    // Divides by the largest power of two, a must not be zero
+    let bits = trailing_zeros(a);
+    a.drain(..bits / 32);
+    let bits = bits % 32;
+    if bits > 0 {
+        for i in 0..a.len() {
+            let high = a.get(i + 1).map_or(0, |digit| digit << (32 - bits));
+            a[i] = (a[i] >> bits) | high;
+        }
+    }
+    trim(a);
+}
+
+
+fn sub_assign_magnitude(a: &mut Vec<u32>, b: &[u32]) {
+    // In place, a must not be smaller than b
+    let mut borrow = 0_u64;
+    for (i, digit) in a.iter_mut().enumerate() {
+        if i >= b.len() && borrow == 0 {
+            break
+        }
+        let subtrahend = *b.get(i).unwrap_or(&0) as u64 + borrow;
+        let value = *digit as u64;
+        borrow = if value < subtrahend {1} else {0};
+        *digit = (value + (borrow << 32) - subtrahend) as u32;
+    }
+    trim(a);
+}
+
+
+fn shift_left(a: &[u32], bits: usize) -> Vec<u32> {
+    let mut vec = vec![0_u32; bits / 32];
+    let bits = bits % 32;
+    let mut carry = 0_u32;
+    for digit in a {
+        if bits > 0 {
+            vec.push((digit << bits) | carry);
+            carry = digit >> (32 - bits);
+        } else {
+            vec.push(*digit);
+        }
+    }
+    vec.push(carry);
+    trim(&mut vec);
+    vec
+}
+
+
+fn trailing_zeros(a: &[u32]) -> usize {
+    // a must not be zero
+    let words = a.iter().take_while(|digit| **digit == 0).count();
+    words * 32 + a[words].trailing_zeros() as usize
+}
+
+
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    // Binary long division, b must not be zero
+    let mut quotient = vec![0_u32; a.len()];
+    let mut rem: Vec<u32> = Vec::new();
+    for i in (0..a.len() * 32).rev() {
+        let bit = (a[i / 32] >> (i % 32)) & 1;
+        let mut carry = bit;
+        for digit in rem.iter_mut() {
+            let next = *digit >> 31;
+            *digit = (*digit << 1) | carry;
+            carry = next;
+        }
+        if carry > 0 {
+            rem.push(carry);
+        }
+        if cmp_magnitude(&rem, b) != Ordering::Less {
+            rem = sub_magnitude(&rem, b);
+            quotient[i / 32] |= 1 << (i % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, rem)
+}
+
+
+impl BigInt {
+    fn new(is_negative: bool, mut digits: Vec<u32>) -> BigInt {
+        trim(&mut digits);
+        let is_negative = is_negative && !digits.is_empty();
+        BigInt {is_negative, digits}
+    }
+
+    pub fn from_i64(n: i64) -> BigInt {
+        let magnitude = n.unsigned_abs();
+        BigInt::new(n < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+
+    pub fn parse(num_str: &str) -> Option<BigInt> {
+        let (is_negative, digits) = match num_str.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, num_str),
+        };
+        if digits.is_empty() {
+            return None
+        }
+        let mut magnitude = Vec::new();
+        for c in digits.chars() {
+            let digit = c.to_digit(10)?;
+            magnitude = add_magnitude(&mul_magnitude(&magnitude, &[10]), &[digit]);
+        }
+        Some(BigInt::new(is_negative, magnitude))
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None
+        }
+        let mut magnitude = 0_i128;
+        for digit in self.digits.iter().rev() {
+            magnitude = (magnitude << 32) | *digit as i128;
+        }
+        i64::try_from(if self.is_negative {-magnitude} else {magnitude}).ok()
+    }
+
+    pub fn to_float(&self) -> f64 {
+        let mut value = 0.0;
+        for digit in self.digits.iter().rev() {
+            value = value * 4294967296.0 + *digit as f64;
+        }
+        if self.is_negative {-value} else {value}
+    }
+
+    pub fn to_scaled_float(&self) -> (f64, i64) {
+        // (m, k) with self = m * 2^(32k) up to rounding, so that a number beyond the range of f64 keeps its leading digits
+        let k = self.digits.len().saturating_sub(3);
+        let value = BigInt::new(self.is_negative, self.digits[k..].to_vec()).to_float();
+        (value, k as i64)
+    }
+
+    pub fn bits(&self) -> usize {
+        // The number of binary digits of the magnitude
+        match self.digits.last() {
+            Some(digit) => self.digits.len() * 32 - digit.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    pub fn neg(&self) -> BigInt {
+        BigInt::new(!self.is_negative, self.digits.clone())
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::new(false, self.digits.clone())
+    }
+
+    pub fn add(&self, other: &BigInt) -> BigInt {
+        if self.is_negative == other.is_negative {
+            return BigInt::new(self.is_negative, add_magnitude(&self.digits, &other.digits))
+        }
+        match cmp_magnitude(&self.digits, &other.digits) {
+            Ordering::Less => BigInt::new(other.is_negative, sub_magnitude(&other.digits, &self.digits)),
+            _ => BigInt::new(self.is_negative, sub_magnitude(&self.digits, &other.digits)),
+        }
+    }
+
+    pub fn mul(&self, other: &BigInt) -> BigInt {
+        BigInt::new(self.is_negative != other.is_negative, mul_magnitude(&self.digits, &other.digits))
+    }
+
+    pub fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        // Truncated toward zero like i64 / and %
+        if other.is_zero() {
+            return None
+        }
+        let (quotient, rem) = match other.digits.as_slice() {
+            [divisor] => {
+                let (quotient, rem) = div_rem_small(&self.digits, *divisor);
+                (quotient, vec![rem])
+            },
+            _ => div_rem_magnitude(&self.digits, &other.digits),
+        };
+        Some((
+            BigInt::new(self.is_negative != other.is_negative, quotient),
+            BigInt::new(self.is_negative, rem),
+        ))
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        // Binary gcd, which only shifts and subtracts
+        if self.is_zero() {
+            return other.abs()
+        }
+        if other.is_zero() {
+            return self.abs()
+        }
+        let shift = trailing_zeros(&self.digits).min(trailing_zeros(&other.digits));
+        let mut a = self.digits.clone();
+        remove_trailing_zeros(&mut a);
+        let mut b = other.digits.clone();
+        while !b.is_empty() {
+            remove_trailing_zeros(&mut b);
+            if cmp_magnitude(&a, &b) == Ordering::Greater {
+                std::mem::swap(&mut a, &mut b);
+            }
+            sub_assign_magnitude(&mut b, &a);
+        }
+        BigInt::new(false, shift_left(&a, shift))
+    }
+
+    pub fn sqrt(&self) -> Option<BigInt> {
+        // Floor of the square root by Newton's method
+        if self.is_negative {
+            return None
+        }
+        if self.is_zero() {
+            return Some(self.clone())
+        }
+        let bits = self.digits.len() * 32;
+        let mut digits = vec![0_u32; bits / 64 + 1];
+        digits[bits / 64] = 1 << ((bits / 2) % 32);
+        let mut x = BigInt::new(false, digits);
+        loop {
+            let (quotient, _) = self.div_rem(&x)?;
+            let (y, _) = x.add(&quotient).div_rem(&BigInt::from_i64(2))?;
+            if y.cmp(&x) != Ordering::Less {
+                return Some(x)
+            }
+            x = y;
+        }
+    }
+}
+
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.is_negative, other.is_negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.digits, &other.digits),
+            (true, true) => cmp_magnitude(&other.digits, &self.digits),
+        }
+    }
+}
+
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0")
+        }
+        let mut chunks = Vec::new();
+        let mut magnitude = self.digits.clone();
+        while !magnitude.is_empty() {
+            let (quotient, rem) = div_rem_small(&magnitude, 1_000_000_000);
+            chunks.push(rem);
+            magnitude = quotient;
+        }
+        let mut string = if self.is_negative {"-".to_string()} else {String::new()};
+        string += &format!("{}", chunks.pop().unwrap());
+        for chunk in chunks.iter().rev() {
+            string += &format!("{:09}", chunk);
+        }
+        write!(f, "{}", string)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        BigInt::parse(s).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(format!("{}", big("-123456789012345678901234567890")), "-123456789012345678901234567890".to_string());
+    }
+
+    #[test]
+    fn parse_zero() {
+        assert_eq!(format!("{}", big("-0")), "0".to_string());
+    }
+
+    #[test]
+    fn parse_not_number() {
+        assert_eq!(BigInt::parse("12a"), None);
+    }
+
+    #[test]
+    fn from_i64_min() {
+        assert_eq!(format!("{}", BigInt::from_i64(i64::MIN)), "-9223372036854775808".to_string());
+    }
+
+    #[test]
+    fn to_i64_fit() {
+        assert_eq!(big("-9223372036854775808").to_i64(), Some(i64::MIN));
+    }
+
+    #[test]
+    fn to_i64_overflow() {
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+    }
+
+    #[test]
+    fn to_float_big() {
+        assert_eq!(big("18446744073709551616").to_float(), 18446744073709551616.0);
+    }
+
+    #[test]
+    fn add_carry() {
+        assert_eq!(big("18446744073709551615").add(&big("1")), big("18446744073709551616"));
+    }
+
+    #[test]
+    fn add_different_sign() {
+        assert_eq!(big("-18446744073709551616").add(&big("1")), big("-18446744073709551615"));
+    }
+
+    #[test]
+    fn add_to_zero() {
+        assert_eq!(big("99999999999999999999").add(&big("-99999999999999999999")), big("0"));
+    }
+
+    #[test]
+    fn mul_big() {
+        assert_eq!(big("-9223372036854775808").mul(&big("9223372036854775808")), big("-85070591730234615865843651857942052864"));
+    }
+
+    #[test]
+    fn div_rem_normal() {
+        assert_eq!(big("85070591730234615865843651857942052865").div_rem(&big("-9223372036854775808")),
+            Some((big("-9223372036854775808"), big("1"))));
+    }
+
+    #[test]
+    fn div_rem_zero() {
+        assert_eq!(big("1").div_rem(&big("0")), None);
+    }
+
+    #[test]
+    fn gcd_big() {
+        assert_eq!(big("36893488147419103232").gcd(&big("-24")), big("8"));
+    }
+
+    #[test]
+    fn div_rem_single_digit() {
+        assert_eq!(big("-36893488147419103233").div_rem(&big("10")), Some((big("-3689348814741910323"), big("-3"))));
+    }
+
+    #[test]
+    fn bits_power_of_two() {
+        assert_eq!(big("36893488147419103232").bits(), 66);
+        assert_eq!(big("-1").bits(), 1);
+        assert_eq!(big("0").bits(), 0);
+    }
+
+    #[test]
+    fn gcd_zero() {
+        assert_eq!(big("0").gcd(&big("-36893488147419103232")), big("36893488147419103232"));
+    }
+
+    #[test]
+    fn gcd_odd() {
+        assert_eq!(big("515377520732011331036461129765621272702107522001").gcd(&big("3486784401")), big("3486784401"));
+    }
+
+    #[test]
+    fn to_scaled_float_beyond_f64() {
+        let (value, k) = big(&format!("3{}", "0".repeat(400))).to_scaled_float();
+        assert_eq!(k, 39);
+        assert!((value.log10() + k as f64 * 32.0 * 2_f64.log10() - 3_f64.log10() - 400.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sqrt_square() {
+        assert_eq!(big("85070591730234615865843651857942052864").sqrt(), Some(big("9223372036854775808")));
+    }
+
+    #[test]
+    fn sqrt_floor() {
+        assert_eq!(big("99").sqrt(), Some(big("9")));
+    }
+
+    #[test]
+    fn sqrt_negative() {
+        assert_eq!(big("-4").sqrt(), None);
+    }
+
+    #[test]
+    fn cmp_sign() {
+        assert!(big("-100000000000000000000") < big("1"));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::math_utility::math_utility::sqrt;
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex {re, im}
+    }
+
+    pub fn add(&self, other: &Complex) -> Complex {
+        Complex {re: self.re + other.re, im: self.im + other.im}
+    }
+
+    pub fn sub(&self, other: &Complex) -> Complex {
+        Complex {re: self.re - other.re, im: self.im - other.im}
+    }
+
+    pub fn mul(&self, other: &Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+
+    pub fn div(&self, other: &Complex) -> Complex {
+        // other is scaled near 1 first so that its square cannot overflow, a power of two changes no digit
+        let scale = power_of_two_scale(other);
+        let (re, im) = (other.re * scale, other.im * scale);
+        let norm = re * re + im * im;
+        Complex {
+            re: (self.re * re + self.im * im) / norm * scale,
+            im: (self.im * re - self.re * im) / norm * scale,
+        }
+    }
+
+    pub fn abs(&self) -> f64 {
+        let scale = power_of_two_scale(self);
+        let (re, im) = (self.re * scale, self.im * scale);
+        sqrt(re * re + im * im) / scale
+    }
+
+    pub fn norm(&self) -> f64 {
+        // The square of abs, which compares the same without a square root
+        self.re * self.re + self.im * self.im
+    }
+}
+
+
+fn power_of_two_scale(z: &Complex) -> f64 {
+    // 2^-e, with 2^e the power of two at or below the larger part of z
+    let larger = z.re.abs().max(z.im.abs());
+    if larger == 0.0 || !larger.is_finite() {
+        return 1.0
+    }
+    let exponent = (((larger.to_bits() >> 52) & 0x7ff) as i64 - 1023).min(1022);
+    f64::from_bits(((1023 - exponent) as u64) << 52)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complex_add() {
+        let lhs = Complex::new(1.0, 2.0);
+        let rhs = Complex::new(3.0, -1.0);
+        assert_eq!(lhs.add(&rhs), Complex::new(4.0, 1.0));
+    }
+
+    #[test]
+    fn complex_sub() {
+        let lhs = Complex::new(1.0, 2.0);
+        let rhs = Complex::new(3.0, -1.0);
+        assert_eq!(lhs.sub(&rhs), Complex::new(-2.0, 3.0));
+    }
+
+    #[test]
+    fn complex_mul() {
+        let lhs = Complex::new(1.0, 2.0);
+        let rhs = Complex::new(3.0, -1.0);
+        assert_eq!(lhs.mul(&rhs), Complex::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn complex_div() {
+        let lhs = Complex::new(5.0, 5.0);
+        let rhs = Complex::new(3.0, -1.0);
+        assert_eq!(lhs.div(&rhs), Complex::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn complex_abs() {
+        let value = Complex::new(3.0, -4.0);
+        assert_eq!(value.abs(), 5.0);
+    }
+}
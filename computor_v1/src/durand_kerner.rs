@@ -0,0 +1,249 @@
+use crate::complex::Complex;
+use crate::math_utility::math_utility::{nth_root, cos, PI};
+
+
+fn evaluate(coefficients: &[f64], z: &Complex) -> Complex {
+    let mut value = Complex::new(0.0, 0.0);
+    for n in coefficients.iter().rev() {
+        value = value.mul(z).add(&Complex::new(*n, 0.0));
+    }
+    value
+}
+
+
+// 2^500, products are renormalised by it so that they stay far from overflow and underflow
+const SCALE: f64 = 3.273390607896142e150;
+
+const MAX_ITERATIONS: usize = 10000;
+
+// An iteration costs the square of the degree, so high degrees get fewer of them
+const MAX_WORK: usize = 50_000_000;
+
+
+#[derive(Clone, Copy)]
+struct Scaled {
+    // value * SCALE^exponent
+    value: Complex,
+    exponent: i64,
+}
+
+
+impl Scaled {
+    fn new(value: Complex) -> Scaled {
+        Scaled {value, exponent: 0}.normalised()
+    }
+
+    fn normalised(mut self) -> Scaled {
+        let magnitude = |z: &Complex| z.re.abs().max(z.im.abs());
+        let m = magnitude(&self.value);
+        if m <= SCALE && (m >= 1.0 / SCALE || m == 0.0) {
+            return self
+        }
+        while magnitude(&self.value) > SCALE && magnitude(&self.value).is_finite() {
+            self.value = self.value.mul(&Complex::new(1.0 / SCALE, 0.0));
+            self.exponent += 1;
+        }
+        while magnitude(&self.value) != 0.0 && magnitude(&self.value) < 1.0 / SCALE {
+            self.value = self.value.mul(&Complex::new(SCALE, 0.0));
+            self.exponent -= 1;
+        }
+        self
+    }
+
+    fn mul(&self, z: &Complex) -> Scaled {
+        Scaled {value: self.value.mul(z), exponent: self.exponent}.normalised()
+    }
+
+    fn div(&self, other: &Scaled) -> Complex {
+        // Past three steps of SCALE the quotient is out of the range of f64 anyway
+        let exponent = self.exponent - other.exponent;
+        let factor = Complex::new(if exponent > 0 {SCALE} else {1.0 / SCALE}, 0.0);
+        let mut value = self.value.div(&other.value);
+        for _ in 0..exponent.abs().min(3) {
+            value = value.mul(&factor);
+        }
+        value
+    }
+}
+
+
+fn evaluate_scaled(coefficients: &[f64], z: &Complex) -> Scaled {
+    // Outside the unit circle p(z) = z^n q(1/z), with q the reversed polynomial, so no power of z overflows
+    if z.norm() <= 1.0 {
+        return Scaled::new(evaluate(coefficients, z))
+    }
+    let w = Complex::new(1.0, 0.0).div(z);
+    let mut reversed = Complex::new(0.0, 0.0);
+    for n in coefficients {
+        reversed = reversed.mul(&w).add(&Complex::new(*n, 0.0));
+    }
+    let mut value = Scaled::new(reversed);
+    for _ in 1..coefficients.len() {
+        value = value.mul(z);
+    }
+    value
+}
+
+
+fn weierstrass_correction(coefficients: &[f64], roots: &[Complex], i: usize) -> Complex {
+    // p(z_i) / (a_n * prod_{j != i} (z_i - z_j))
+    let leading = coefficients[coefficients.len() - 1];
+    let mut denominator = Scaled::new(Complex::new(leading, 0.0));
+    for (j, root) in roots.iter().enumerate() {
+        if j != i {
+            denominator = denominator.mul(&roots[i].sub(root));
+        }
+    }
+    evaluate_scaled(coefficients, &roots[i]).div(&denominator)
+}
+
+
+fn newton_bound(coefficients: &[f64], z: &Complex) -> f64 {
+    // n * (|p(z) / p'(z)| + the rounding error of p(z) / |p'(z)|)
+    let degree = (coefficients.len() - 1) as f64;
+    let is_inside = z.norm() <= 1.0;
+    let x = if is_inside {*z} else {Complex::new(1.0, 0.0).div(z)};
+    let modulus = x.abs();
+    let mut value = Complex::new(0.0, 0.0);
+    let mut derivative = Complex::new(0.0, 0.0);
+    let mut rounding = 0.0;
+    // Outside the unit circle the reversed polynomial q(w) = w^n p(1/w) is evaluated instead
+    let order: Vec<&f64> = if is_inside {coefficients.iter().rev().collect()} else {coefficients.iter().collect()};
+    for n in order {
+        derivative = derivative.mul(&x).add(&value);
+        value = value.mul(&x).add(&Complex::new(*n, 0.0));
+        rounding = rounding * modulus + n.abs();
+    }
+    let (ratio, rounding) = if is_inside {
+        (value.div(&derivative), rounding / derivative.abs())
+    } else {
+        // p / p' = z q / (n q - w q'), where the powers of z cancel out
+        let denominator = value.mul(&Complex::new(degree, 0.0)).sub(&x.mul(&derivative));
+        (z.mul(&value).div(&denominator), z.abs() * rounding / denominator.abs())
+    };
+    let bound = degree * (ratio.abs() + f64::EPSILON * rounding);
+    // p'(z) is zero at a multiple root, where nothing is known
+    if bound.is_nan() {f64::INFINITY} else {bound}
+}
+
+
+fn seed_radius(coefficients: &[f64]) -> f64 {
+    // The geometric mean of the moduli of the nonzero roots, found without forming the quotient that may overflow
+    let degree = coefficients.len() - 1;
+    let lowest = match coefficients.iter().position(|n| *n != 0.0) {
+        Some(k) if k < degree => k,
+        _ => return 1.0,
+    };
+    let count = (degree - lowest) as u32;
+    nth_root(coefficients[lowest].abs(), count) / nth_root(coefficients[degree].abs(), count)
+}
+
+
+pub fn durand_kerner(coefficients: &[f64]) -> Vec<Complex> {
+    // coefficients[k] is the coefficient of x^k
+    let degree = coefficients.len() - 1;
+    let radius = seed_radius(coefficients);
+    let mut roots: Vec<Complex> = (0..degree).map(|k| {
+        // The offset keeps the guesses off the real axis, where real coefficients would hold them
+        let angle = 2.0 * PI * k as f64 / degree as f64 + 0.4;
+        Complex::new(radius * cos(angle), radius * cos(angle - PI / 2.0))
+    }).collect();
+    // A multiple root converges slowly, its error bound tells how far it got
+    for _ in 0..(MAX_WORK / (degree * degree).max(1)).min(MAX_ITERATIONS) {
+        let mut is_converged = true;
+        for i in 0..degree {
+            let correction = weierstrass_correction(coefficients, &roots, i);
+            if !correction.re.is_finite() || !correction.im.is_finite() {
+                is_converged = false;
+                continue
+            }
+            roots[i] = roots[i].sub(&correction);
+            if correction.abs() > 1e-15 * roots[i].abs() {
+                is_converged = false;
+            }
+        }
+        if is_converged {
+            break;
+        }
+    }
+    roots
+}
+
+
+pub fn error_bounds(coefficients: &[f64], roots: &[Complex]) -> Vec<f64> {
+    // p'(z) / p(z) is the sum of 1 / (z - root), so a disc of radius n * |p(z) / p'(z)| around z contains a root,
+    // whether the iteration converged or not
+    roots.iter().map(|root| newton_bound(coefficients, root)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_real() {
+        let coefficients = vec![-2.0, 0.0, 1.0];
+        assert_eq!(evaluate(&coefficients, &Complex::new(3.0, 0.0)), Complex::new(7.0, 0.0));
+    }
+
+    #[test]
+    fn evaluate_complex() {
+        let coefficients = vec![1.0, 0.0, 1.0];
+        assert_eq!(evaluate(&coefficients, &Complex::new(0.0, 1.0)), Complex::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn durand_kerner_quadratic() {
+        let coefficients = vec![-2.0, -1.0, 1.0];
+        let mut roots: Vec<f64> = durand_kerner(&coefficients).iter().map(|z| z.re).collect();
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((roots[0] + 1.0).abs() < 1e-12);
+        assert!((roots[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn durand_kerner_quintic() {
+        // (x - 1)(x - 2)(x - 3)(x^2 + 1)
+        let coefficients = vec![-6.0, 11.0, -12.0, 12.0, -6.0, 1.0];
+        let roots = durand_kerner(&coefficients);
+        for root in &roots {
+            assert!(evaluate(&coefficients, root).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn error_bounds_not_converged() {
+        // (x - 1)^2 at 1.5 is 0.25 with a derivative of 1, the root 1 is 0.5 away
+        let coefficients = vec![1.0, -2.0, 1.0];
+        let bounds = error_bounds(&coefficients, &[Complex::new(1.5, 0.0), Complex::new(0.0, 0.0)]);
+        assert!(bounds[0] >= 0.5 && bounds[0] < 0.51);
+        assert!(bounds[1] >= 1.0 && bounds[1] < 1.01);
+    }
+
+    #[test]
+    fn error_bounds_small() {
+        let coefficients = vec![-6.0, 11.0, -12.0, 12.0, -6.0, 1.0];
+        let roots = durand_kerner(&coefficients);
+        for bound in error_bounds(&coefficients, &roots) {
+            assert!(bound < 1e-10);
+        }
+    }
+
+    #[test]
+    fn evaluate_scaled_overflow() {
+        // 2^2000 is beyond f64, its quotient by 2^1999 is not
+        let mut coefficients = vec![0.0; 2001];
+        coefficients[2000] = 1.0;
+        let value = evaluate_scaled(&coefficients, &Complex::new(2.0, 0.0));
+        coefficients[2000] = 0.0;
+        coefficients[1999] = 1.0;
+        assert_eq!(value.div(&evaluate_scaled(&coefficients, &Complex::new(2.0, 0.0))), Complex::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn seed_radius_cauchy_bounds() {
+        // Between 1 / 2 and 2 for x^n - 1
+        assert_eq!(seed_radius(&[-1.0, 0.0, 0.0, 0.0, 0.0, 1.0]), 1.0);
+    }
+}
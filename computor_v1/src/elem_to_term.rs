@@ -1,4 +1,6 @@
-use crate::term::{Elem, Coefficient, Term};
+use crate::term::{Elem, Token, Coefficient, Term};
+use crate::error::{ErrorKind, ParseError, Span};
+use crate::expression::{parse_expression, expand_expression};
 
 
 #[derive(PartialEq, PartialOrd)]
@@ -9,129 +11,236 @@ enum TermStep {
     Prod,
     Variable,
     Power,
+    PowerMinus,
     Degree,
 }
 
 
-fn check_and_push_term(elems: &Vec<Elem>, terms: &mut Vec<Term>) -> Result<(), String> {
-    let mut coefficient = Coefficient::NumInt(1);
-    let mut degree = 0;
+fn check_and_push_term(tokens: &[Token], terms: &mut Vec<Term>) -> Result<(), ParseError> {
+    // A term is a product of numbers and powers of X, written with * or side by side
+    let mut coefficient: Option<Coefficient> = None;
+    let mut degree: i64 = 0;
     let mut is_plus = true;
+    // / divides by the single factor that follows it, X or X^n counting negatively
+    let mut is_divisor = false;
+    let mut degree_sign = 1;
     let mut term_step = TermStep::Start;
-    for elem in elems {
-        match elem {
+    for token in tokens {
+        let misplaced_operator = ParseError::new(ErrorKind::MisplacedOperator, token.span);
+        let unexpected_value = ParseError::new(ErrorKind::UnexpectedValue, token.span);
+        // A degree out of the i64 range points at the term up to the token
+        let degree_too_large = ParseError::new(ErrorKind::DegreeTooLarge, Span::new(tokens[0].span.start, token.span.end));
+        let value = match &token.elem {
+            Elem::Minus if term_step == TermStep::Power => {
+                term_step = TermStep::PowerMinus;
+                continue
+            },
             Elem::Minus | Elem::Plus => {
                 if term_step >= TermStep::PlusMinus {
-                    return Err("Incorrect syntax".to_string())
+                    return Err(misplaced_operator)
                 }
-                if let Elem::Minus = elem {
+                if let Elem::Minus = token.elem {
                     is_plus = false;
-                    coefficient = Coefficient::NumInt(-1);
                 }
                 term_step = TermStep::PlusMinus;
+                continue
             },
-            Elem::NumInt(n) => {
-                if term_step == TermStep::Power {
-                    degree = *n;
-                    term_step = TermStep::Degree;
-                } else {
-                    if term_step >= TermStep::Value {
-                        return Err("Incorrect syntax".to_string())
-                    }
-                    if is_plus {
-                        coefficient = Coefficient::NumInt(*n);
-                    } else {
-                        coefficient = Coefficient::NumInt(*n).mul_minus();
-                    }
-                    term_step = TermStep::Value;
-                }
-            },
-            Elem::NumFloat(n) => {
-                if term_step >= TermStep::Value {
-                    return Err("Incorrect syntax".to_string())
-                }
-                let value = n * if is_plus {1 as f64} else {-1 as f64};
-                coefficient = Coefficient::NumFloat(value);
-                term_step = TermStep::Value;
+            Elem::NumInt(n) if term_step == TermStep::Power || term_step == TermStep::PowerMinus => {
+                // X^n and X^-n count n and -n instead of the 1 already added for X
+                let power = if term_step == TermStep::PowerMinus {-n} else {*n};
+                degree = match (power - 1).checked_mul(degree_sign).and_then(|n| degree.checked_add(n)) {
+                    Some(n) => n,
+                    None => return Err(degree_too_large),
+                };
+                term_step = TermStep::Degree;
+                continue
             },
-            Elem::Prod => {
-                if term_step != TermStep::Value {
-                    return Err("Incorrect syntax".to_string())
+            Elem::NumInt(n) => Coefficient::NumInt(*n),
+            Elem::NumFloat(n) => Coefficient::NumFloat(*n),
+            Elem::NumFraction(f) => Coefficient::NumFraction(*f),
+            Elem::NumBigFraction(f) => Coefficient::from_big_fraction(f.clone()),
+            Elem::NumBig(n) => Coefficient::NumBig(n.clone()),
+            Elem::Prod | Elem::Div => {
+                if term_step != TermStep::Value && term_step != TermStep::Variable && term_step != TermStep::Degree {
+                    return Err(misplaced_operator)
                 }
+                is_divisor = token.elem == Elem::Div;
                 term_step = TermStep::Prod;
+                continue
             },
             Elem::X => {
-                if term_step >= TermStep::Variable {
-                    return Err("Incorrect syntax".to_string())
+                if term_step == TermStep::Power || term_step == TermStep::PowerMinus {
+                    return Err(unexpected_value)
                 }
-                degree = 1;
+                degree_sign = if is_divisor {-1} else {1};
+                is_divisor = false;
+                degree = match degree.checked_add(degree_sign) {
+                    Some(n) => n,
+                    None => return Err(degree_too_large),
+                };
                 term_step = TermStep::Variable;
+                continue
             },
             Elem::Power => {
                 if term_step != TermStep::Variable {
-                    return Err("Incorrect syntax".to_string())
+                    return Err(misplaced_operator)
                 }
                 term_step = TermStep::Power;
+                continue
             },
-            _ => {},
+            _ => continue,
+        };
+        // Two numbers side by side are taken as a typing mistake rather than a product
+        if term_step == TermStep::Value || term_step == TermStep::Power || term_step == TermStep::PowerMinus {
+            return Err(unexpected_value)
         }
+        let product = match coefficient {
+            Some(c) if !is_divisor => c.mul(&value),
+            None if !is_divisor => value,
+            c => match c.unwrap_or(Coefficient::NumInt(1)).div(&value) {
+                Some(quotient) => quotient,
+                None => return Err(ParseError::new(ErrorKind::DivisionByZero, token.span)),
+            },
+        };
+        coefficient = Some(product);
+        is_divisor = false;
+        term_step = TermStep::Value;
     }
     if term_step != TermStep::Value && term_step != TermStep::Variable && term_step != TermStep::Degree {
-        return Err("Incorrect syntax".to_string())
+        // The term stops right after an operator
+        return Err(match tokens.last() {
+            Some(token) => ParseError::new(ErrorKind::MisplacedOperator, token.span),
+            None => ParseError::new(ErrorKind::MissingTerm, Span::new(0, 0)),
+        })
+    }
+    let coefficient = coefficient.unwrap_or(Coefficient::NumInt(1));
+    terms.push(Term {coefficient: if is_plus {coefficient} else {coefficient.mul_minus()}, degree});
+    Ok(())
+}
+
+
+fn group_has_variable<'a>(elems: impl Iterator<Item = &'a Elem>, open: &Elem) -> bool {
+    // elems starts at the parenthesis that opens the group, read forward or backward
+    let mut depth = 0;
+    for elem in elems {
+        match elem {
+            Elem::X => return true,
+            Elem::LeftParen | Elem::RightParen if elem == open => depth += 1,
+            Elem::LeftParen | Elem::RightParen => depth -= 1,
+            _ => {},
+        }
+        if depth == 0 {
+            return false
+        }
+    }
+    false
+}
+
+
+pub fn divides_by_variable(tokens: &[Token]) -> bool {
+    // Even when X cancels out, as in X / X, the equation stays undefined at X = 0
+    let elems: Vec<&Elem> = tokens.iter().map(|token| &token.elem).collect();
+    (0..elems.len()).any(|i| match (elems[i], elems.get(i + 1)) {
+        (Elem::Div, Some(Elem::X)) => true,
+        (Elem::Div, Some(Elem::LeftParen)) => group_has_variable(elems[i + 1..].iter().copied(), &Elem::LeftParen),
+        (Elem::Power, Some(Elem::Minus)) => match elems[..i].last() {
+            Some(Elem::X) => true,
+            Some(Elem::RightParen) => group_has_variable(elems[..i].iter().rev().copied(), &Elem::RightParen),
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
+
+fn expand_side(tokens: &[Token], end: Span) -> Result<Vec<Term>, ParseError> {
+    let expression = parse_expression(tokens, end)?;
+    expand_expression(&expression)
+}
+
+
+fn end_span(tokens: &[Token]) -> Span {
+    match tokens.last() {
+        Some(token) => Span::new(token.span.end, token.span.end + 1),
+        None => Span::new(0, 1),
+    }
+}
+
+
+fn group_elem_to_term(tokens: &[Token]) -> Result<(Vec<Term>, Vec<Term>), ParseError> {
+    let equals: Vec<&Token> = tokens.iter().filter(|token| token.elem == Elem::Equal).collect();
+    match equals.len() {
+        0 => Err(ParseError::new(ErrorKind::MissingEqual, end_span(tokens))),
+        1 => {
+            let sides: Vec<&[Token]> = tokens.split(|token| token.elem == Elem::Equal).collect();
+            Ok((expand_side(sides[0], equals[0].span)?, expand_side(sides[1], end_span(tokens))?))
+        },
+        _ => Err(ParseError::new(ErrorKind::MultipleEquals, equals[1].span)),
     }
-    terms.push(Term {coefficient, degree});
-    return Ok(());
 }
 
 
-pub fn elem_to_term(elems: Vec<Elem>) -> Result<(Vec<Term>, Vec<Term>), String> {
+pub fn elem_to_term(tokens: Vec<Token>) -> Result<(Vec<Term>, Vec<Term>), ParseError> {
+    if tokens.iter().any(|token| matches!(token.elem, Elem::LeftParen | Elem::RightParen | Elem::Constant(_) | Elem::Function(_))) {
+        return group_elem_to_term(&tokens);
+    }
+    let end = end_span(&tokens);
     let mut vec_left = Vec::new();
     let mut vec_right = Vec::new();
-    let mut elem_stock = Vec::new();
+    let mut token_stock = Vec::new();
     let mut is_left = true;
 
-    for elem in elems {
-        match elem {
+    for token in tokens {
+        match token.elem {
+            // The minus of X^-n belongs to the power, not to the next term
+            Elem::Minus if matches!(token_stock.last(), Some(Token {elem: Elem::Power, ..})) => token_stock.push(token),
             Elem::Plus | Elem::Minus => {
-                if elem_stock.len() == 0 {
-                    elem_stock.push(elem);
+                if token_stock.is_empty() {
+                    token_stock.push(token);
                 } else {
-                    check_and_push_term(&elem_stock,
+                    check_and_push_term(&token_stock,
                         if is_left {&mut vec_left} else {&mut vec_right})?;
-                    elem_stock.clear();
-                    elem_stock.push(elem);
+                    token_stock.clear();
+                    token_stock.push(token);
                 }
             }
             Elem::Equal => {
                 if is_left {
-                    check_and_push_term(&elem_stock,
-                        if is_left {&mut vec_left} else {&mut vec_right})?;
-                    elem_stock.clear();
+                    if token_stock.is_empty() {
+                        return Err(ParseError::new(ErrorKind::MissingTerm, token.span));
+                    }
+                    check_and_push_term(&token_stock, &mut vec_left)?;
+                    token_stock.clear();
                     is_left = false;
                 } else {
-                    return Err("There were multiple equals".to_string());
+                    return Err(ParseError::new(ErrorKind::MultipleEquals, token.span));
                 }
             },
-            _ => elem_stock.push(elem),
+            _ => token_stock.push(token),
         }
     }
-    if !elem_stock.is_empty() {
-        check_and_push_term(&elem_stock, &mut vec_right)?;
+    if !token_stock.is_empty() {
+        check_and_push_term(&token_stock, &mut vec_right)?;
     }
     if is_left {
-        return Err("There was no equal".to_string());
+        return Err(ParseError::new(ErrorKind::MissingEqual, end));
     }
-    return Ok((vec_left, vec_right));
+    Ok((vec_left, vec_right))
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fraction::Fraction;
+
+    fn tokens(elems: Vec<Elem>) -> Vec<Token> {
+        elems.into_iter().enumerate().map(|(i, elem)| Token::new(elem, i, i + 1)).collect()
+    }
 
     #[test]
     fn check_and_push_term_x() {
-        let elems = vec![Elem::X];
+        let elems = tokens(vec![Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -140,7 +249,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_int() {
-        let elems = vec![Elem::NumInt(2)];
+        let elems = tokens(vec![Elem::NumInt(2)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -149,7 +258,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_float() {
-        let elems = vec![Elem::NumFloat(2.0)];
+        let elems = tokens(vec![Elem::NumFloat(2.0)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -158,7 +267,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_plus_int() {
-        let elems = vec![Elem::Plus, Elem::NumInt(2)];
+        let elems = tokens(vec![Elem::Plus, Elem::NumInt(2)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -167,7 +276,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_minus_int() {
-        let elems = vec![Elem::Minus, Elem::NumInt(2)];
+        let elems = tokens(vec![Elem::Minus, Elem::NumInt(2)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -176,7 +285,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_plus_float() {
-        let elems = vec![Elem::Plus, Elem::NumFloat(2.0)];
+        let elems = tokens(vec![Elem::Plus, Elem::NumFloat(2.0)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -185,16 +294,35 @@ mod tests {
 
     #[test]
     fn check_and_push_term_minus_float() {
-        let elems = vec![Elem::Minus, Elem::NumFloat(2.0)];
+        let elems = tokens(vec![Elem::Minus, Elem::NumFloat(2.0)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
         assert_eq!(terms[0], Term {coefficient: Coefficient::NumFloat(-2.0), degree: 0});
     }
 
+    #[test]
+    fn check_and_push_term_minus_fraction() {
+        let elems = tokens(vec![Elem::Minus, Elem::NumFraction(Fraction::safe_new(1, 2).unwrap())]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Ok(()));
+        assert_eq!(terms[0], Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(-1, 2).unwrap()), degree: 0});
+    }
+
+    #[test]
+    fn check_and_push_term_minus_big_to_int() {
+        use crate::big_int::BigInt;
+        let elems = tokens(vec![Elem::Minus, Elem::NumBig(BigInt::parse("9223372036854775808").unwrap())]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Ok(()));
+        assert_eq!(terms[0], Term {coefficient: Coefficient::NumInt(i64::MIN), degree: 0});
+    }
+
     #[test]
     fn check_and_push_term_plus_x() {
-        let elems = vec![Elem::Plus, Elem::X];
+        let elems = tokens(vec![Elem::Plus, Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -203,7 +331,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_minus_x() {
-        let elems = vec![Elem::Minus, Elem::X];
+        let elems = tokens(vec![Elem::Minus, Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -212,7 +340,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_prod_int() {
-        let elems = vec![Elem::Minus, Elem::NumInt(2), Elem::Prod, Elem::X];
+        let elems = tokens(vec![Elem::Minus, Elem::NumInt(2), Elem::Prod, Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -221,7 +349,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_prod_float() {
-        let elems = vec![Elem::Minus, Elem::NumFloat(2.0), Elem::Prod, Elem::X];
+        let elems = tokens(vec![Elem::Minus, Elem::NumFloat(2.0), Elem::Prod, Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -230,7 +358,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_non_prod_int() {
-        let elems = vec![Elem::Minus, Elem::NumInt(2), Elem::X];
+        let elems = tokens(vec![Elem::Minus, Elem::NumInt(2), Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -239,7 +367,7 @@ mod tests {
 
     #[test]
     fn check_and_push_term_non_prod_float() {
-        let elems = vec![Elem::Minus, Elem::NumFloat(2.0), Elem::X];
+        let elems = tokens(vec![Elem::Minus, Elem::NumFloat(2.0), Elem::X]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -248,7 +376,7 @@ mod tests {
     
     #[test]
     fn check_and_push_term_power() {
-        let elems = vec![Elem::X, Elem::Power, Elem::NumInt(2)];
+        let elems = tokens(vec![Elem::X, Elem::Power, Elem::NumInt(2)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
@@ -257,50 +385,214 @@ mod tests {
 
     #[test]
     fn check_and_push_term_full() {
-        let elems = vec![Elem::Minus, Elem::NumFloat(2.0), Elem::Prod, Elem::X, Elem::Power, Elem::NumInt(2)];
+        let elems = tokens(vec![Elem::Minus, Elem::NumFloat(2.0), Elem::Prod, Elem::X, Elem::Power, Elem::NumInt(2)]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
         assert_eq!(result, Ok(()));
         assert_eq!(terms[0], Term {coefficient: Coefficient::NumFloat(-2.0), degree: 2});
     }
 
+    #[test]
+    fn check_and_push_term_repeated_prod() {
+        use Elem::*;
+        let elems = tokens(vec![NumInt(3), Prod, X, Prod, X, Prod, NumInt(2)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Ok(()));
+        assert_eq!(terms[0], Term {coefficient: Coefficient::NumInt(6), degree: 2});
+    }
+
+    #[test]
+    fn check_and_push_term_juxtaposition() {
+        use Elem::*;
+        let elems = tokens(vec![Minus, X, Power, NumInt(2), NumFloat(0.5), X]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Ok(()));
+        assert_eq!(terms[0], Term {coefficient: Coefficient::NumFloat(-0.5), degree: 3});
+    }
+
+    #[test]
+    fn check_and_push_term_div() {
+        use Elem::*;
+        let elems = tokens(vec![Minus, NumInt(3), X, Div, NumInt(4), Div, X, Power, NumInt(3)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Ok(()));
+        assert_eq!(terms[0], Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(-3, 4).unwrap()), degree: -2});
+    }
+
+    #[test]
+    fn check_and_push_term_negative_power() {
+        use Elem::*;
+        let elems = tokens(vec![NumInt(2), Div, X, Power, Minus, NumInt(3), X, Power, Minus, NumInt(1)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Ok(()));
+        assert_eq!(terms[0], Term {coefficient: Coefficient::NumInt(2), degree: 2});
+    }
+
+    #[test]
+    fn check_and_push_term_error_power_two_minus() {
+        use Elem::*;
+        let elems = tokens(vec![X, Power, Minus, Minus, NumInt(1)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(3, 4))));
+    }
+
+    #[test]
+    fn elem_to_term_negative_power() {
+        use Elem::*;
+        let elems = tokens(vec![X, Power, Minus, NumInt(1), Minus, NumInt(2), Equal, NumInt(0)]);
+        assert_eq!(elem_to_term(elems), Ok((
+            vec![
+                Term {coefficient: Coefficient::NumInt(1), degree: -1},
+                Term {coefficient: Coefficient::NumInt(-2), degree: 0},
+            ],
+            vec![Term {coefficient: Coefficient::NumInt(0), degree: 0}])));
+    }
+
+    #[test]
+    fn divides_by_variable_cancelled() {
+        use Elem::*;
+        assert!(divides_by_variable(&tokens(vec![X, Div, X, Equal, NumInt(1)])));
+    }
+
+    #[test]
+    fn divides_by_variable_group_power() {
+        use Elem::*;
+        assert!(divides_by_variable(&tokens(vec![LeftParen, NumInt(2), X, RightParen, Power, Minus, NumInt(1), Equal, NumInt(1)])));
+        assert!(!divides_by_variable(&tokens(vec![X, Div, LeftParen, NumInt(2), RightParen, Equal, X])));
+    }
+
+    #[test]
+    fn check_and_push_term_error_div_zero() {
+        use Elem::*;
+        let elems = tokens(vec![X, Div, NumFloat(0.0)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Err(ParseError::new(ErrorKind::DivisionByZero, Span::new(2, 3))));
+    }
+
+    #[test]
+    fn check_and_push_term_error_power_overflow() {
+        use Elem::*;
+        let elems = tokens(vec![X, Div, X, Power, NumInt(i64::MAX), Div, X, Power, NumInt(i64::MAX)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 9))));
+    }
+
+    #[test]
+    fn check_and_push_term_error_variable_overflow() {
+        use Elem::*;
+        let elems = tokens(vec![X, Power, Minus, NumInt(i64::MAX), Div, X, Div, X]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 8))));
+    }
+
+    #[test]
+    fn check_and_push_term_error_power_after_degree() {
+        use Elem::*;
+        let elems = tokens(vec![X, Power, NumInt(2), Power, NumInt(2)]);
+        let mut terms = Vec::new();
+        let result = check_and_push_term(&elems, &mut terms);
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(3, 4))));
+    }
+
     #[test]
     fn check_and_push_term_error_only_plus() {
-        let elems = vec![Elem::Plus];
+        let elems = tokens(vec![Elem::Plus]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
-        assert_eq!(result, Err("Incorrect syntax".to_string()));
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(0, 1))));
     }
 
     #[test]
     fn check_and_push_term_error_only_minus() {
-        let elems = vec![Elem::Minus];
+        let elems = tokens(vec![Elem::Minus]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
-        assert_eq!(result, Err("Incorrect syntax".to_string()));
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(0, 1))));
     }
 
     #[test]
     fn check_and_push_term_error_only_prod() {
-        let elems = vec![Elem::Prod];
+        let elems = tokens(vec![Elem::Prod]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
-        assert_eq!(result, Err("Incorrect syntax".to_string()));
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(0, 1))));
     }
 
     #[test]
     fn check_and_push_term_error_only_power() {
-        let elems = vec![Elem::Power];
+        let elems = tokens(vec![Elem::Power]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
-        assert_eq!(result, Err("Incorrect syntax".to_string()));
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(0, 1))));
     }
 
     #[test]
     fn check_and_push_term_error_two_plus() {
-        let elems = vec![Elem::Plus, Elem::Plus];
+        let elems = tokens(vec![Elem::Plus, Elem::Plus]);
         let mut terms = Vec::new();
         let result = check_and_push_term(&elems, &mut terms);
-        assert_eq!(result, Err("Incorrect syntax".to_string()));
+        assert_eq!(result, Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(1, 2))));
+    }
+
+    #[test]
+    fn elem_to_term_group() {
+        use Elem::*;
+        let elems = tokens(vec![
+            LeftParen, X, Plus, NumInt(1), RightParen, Power, NumInt(2), Equal,
+            NumInt(3), LeftParen, X, Minus, NumInt(2), RightParen]);
+        assert_eq!(elem_to_term(elems), Ok((
+            vec![
+                Term {coefficient: Coefficient::NumInt(1), degree: 0},
+                Term {coefficient: Coefficient::NumInt(2), degree: 1},
+                Term {coefficient: Coefficient::NumInt(1), degree: 2},
+            ],
+            vec![
+                Term {coefficient: Coefficient::NumInt(-6), degree: 0},
+                Term {coefficient: Coefficient::NumInt(3), degree: 1},
+            ])));
+    }
+
+    #[test]
+    fn elem_to_term_group_error_no_equal() {
+        let elems = tokens(vec![Elem::LeftParen, Elem::X, Elem::RightParen]);
+        assert_eq!(elem_to_term(elems), Err(ParseError::new(ErrorKind::MissingEqual, Span::new(3, 4))));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn elem_to_term_group_error_multiple_equals() {
+        let elems = tokens(vec![Elem::LeftParen, Elem::X, Elem::RightParen, Elem::Equal, Elem::X, Elem::Equal, Elem::X]);
+        assert_eq!(elem_to_term(elems), Err(ParseError::new(ErrorKind::MultipleEquals, Span::new(5, 6))));
+    }
+
+    #[test]
+    fn elem_to_term_error_value_after_value() {
+        let elems = tokens(vec![Elem::NumInt(2), Elem::NumInt(3), Elem::Equal, Elem::NumInt(1)]);
+        assert_eq!(elem_to_term(elems), Err(ParseError::new(ErrorKind::UnexpectedValue, Span::new(1, 2))));
+    }
+
+    #[test]
+    fn elem_to_term_error_empty_left() {
+        let elems = tokens(vec![Elem::Equal, Elem::NumInt(1)]);
+        assert_eq!(elem_to_term(elems), Err(ParseError::new(ErrorKind::MissingTerm, Span::new(0, 1))));
+    }
+
+    #[test]
+    fn elem_to_term_error_multiple_equals() {
+        let elems = tokens(vec![Elem::X, Elem::Equal, Elem::NumInt(1), Elem::Equal, Elem::NumInt(1)]);
+        assert_eq!(elem_to_term(elems), Err(ParseError::new(ErrorKind::MultipleEquals, Span::new(3, 4))));
+    }
+
+    #[test]
+    fn elem_to_term_error_no_equal() {
+        let elems = tokens(vec![Elem::X, Elem::Plus, Elem::NumInt(1)]);
+        assert_eq!(elem_to_term(elems), Err(ParseError::new(ErrorKind::MissingEqual, Span::new(3, 4))));
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::term::{MAX_DEGREE, MAX_COEFFICIENT_BITS};
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ErrorKind {
+    UnsupportedChar(char),
+    MalformedNumber,
+    NumberOutOfRange,
+    MisplacedOperator,
+    UnexpectedValue,
+    MissingTerm,
+    MissingEqual,
+    MultipleEquals,
+    UnbalancedParentheses,
+    MixedVariables(char, char),
+    VariableClash(char),
+    DivisionByZero,
+    PolynomialDivisor,
+    VariableInFunction,
+    UnknownFunction(String),
+    NegativeSquareRoot,
+    DegreeTooLarge,
+    ExpansionTooLarge,
+}
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub span: Span,
+}
+
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span {start, end}
+    }
+}
+
+
+impl ParseError {
+    pub fn new(kind: ErrorKind, span: Span) -> ParseError {
+        ParseError {kind, span}
+    }
+}
+
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnsupportedChar(c) => write!(f, "Unsupported characters: {}", c),
+            ErrorKind::MalformedNumber => write!(f, "Malformed number"),
+            ErrorKind::NumberOutOfRange => write!(f, "Number out of range"),
+            ErrorKind::MisplacedOperator => write!(f, "Misplaced operator"),
+            ErrorKind::UnexpectedValue => write!(f, "Unexpected value"),
+            ErrorKind::MissingTerm => write!(f, "Missing term"),
+            ErrorKind::MissingEqual => write!(f, "There was no equal"),
+            ErrorKind::MultipleEquals => write!(f, "There were multiple equals"),
+            ErrorKind::UnbalancedParentheses => write!(f, "Unbalanced parentheses"),
+            ErrorKind::MixedVariables(a, b) => write!(f, "Only one variable can be used: {} and {}", a, b),
+            ErrorKind::VariableClash(c) => write!(f, "The variable {} cannot be told apart from the constant e", c),
+            ErrorKind::DivisionByZero => write!(f, "Division by zero"),
+            ErrorKind::PolynomialDivisor => write!(f, "Only numbers and powers of the variable can divide"),
+            ErrorKind::VariableInFunction => write!(f, "Functions can only be applied to numbers"),
+            ErrorKind::UnknownFunction(name) => write!(f, "Unknown function: {}", name),
+            ErrorKind::NegativeSquareRoot => write!(f, "Square root of a negative number"),
+            ErrorKind::DegreeTooLarge => write!(f, "The degree is above the maximum of {}", MAX_DEGREE),
+            ErrorKind::ExpansionTooLarge => write!(f, "The coefficients of the power would be above the maximum of {} bits", MAX_COEFFICIENT_BITS),
+        }
+    }
+}
+
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_unsupported_char() {
+        let error = ParseError::new(ErrorKind::UnsupportedChar('a'), Span::new(3, 4));
+        assert_eq!(format!("{}", error), "Unsupported characters: a".to_string());
+    }
+
+    #[test]
+    fn display_multiple_equals() {
+        let error = ParseError::new(ErrorKind::MultipleEquals, Span::new(3, 4));
+        assert_eq!(format!("{}", error), "There were multiple equals".to_string());
+    }
+}
@@ -0,0 +1,610 @@
+use crate::term::{Elem, Token, Coefficient, Term, Function, MAX_DEGREE, MAX_COEFFICIENT_BITS};
+use crate::error::{ErrorKind, ParseError, Span};
+use crate::reduce_equation::reduce_equation;
+use crate::utility::{hash_terms_to_sorted_vec, lcm_of_bottoms};
+
+
+#[derive(Debug, PartialEq)]
+pub enum Expression {
+    Value(Coefficient),
+    Variable,
+    Neg(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+    Sub(Box<Expression>, Box<Expression>),
+    // The spans of products and powers point at them when their degree is too large
+    Mul(Box<Expression>, Box<Expression>, Span),
+    // The span of the divisor points at it when it cannot divide
+    Div(Box<Expression>, Box<Expression>, Span),
+    Power(Box<Expression>, i64, Span),
+    // The span of the call points at it when the argument is not a number
+    Function(Function, Box<Expression>, Span),
+}
+
+
+#[derive(PartialEq)]
+enum FactorKind {
+    Number,
+    Variable,
+    Group,
+}
+
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    index: usize,
+    depth: usize,
+    end: Span,
+}
+
+
+fn unexpected_token(token: &Token) -> ParseError {
+    let kind = match token.elem {
+        Elem::NumInt(_) | Elem::NumBig(_) | Elem::NumFloat(_) | Elem::NumFraction(_) | Elem::NumBigFraction(_) | Elem::X | Elem::LeftParen
+            | Elem::Constant(_) | Elem::Function(_) => {
+            ErrorKind::UnexpectedValue
+        },
+        Elem::RightParen => ErrorKind::UnbalancedParentheses,
+        _ => ErrorKind::MisplacedOperator,
+    };
+    ParseError::new(kind, token.span)
+}
+
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Elem> {
+        self.tokens.get(self.index).map(|token| &token.elem)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.index);
+        self.index += 1;
+        token
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression, ParseError> {
+        let mut expression = match self.peek() {
+            Some(Elem::Minus) => {
+                self.next();
+                Expression::Neg(Box::new(self.parse_product()?))
+            },
+            Some(Elem::Plus) => {
+                self.next();
+                self.parse_product()?
+            },
+            _ => self.parse_product()?,
+        };
+        loop {
+            match self.peek() {
+                Some(Elem::Plus) => {
+                    self.next();
+                    expression = Expression::Add(Box::new(expression), Box::new(self.parse_product()?));
+                },
+                Some(Elem::Minus) => {
+                    self.next();
+                    expression = Expression::Sub(Box::new(expression), Box::new(self.parse_product()?));
+                },
+                _ => return Ok(expression),
+            }
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expression, ParseError> {
+        let start = self.tokens.get(self.index).map_or(self.end.start, |token| token.span.start);
+        let (mut expression, mut kind) = self.parse_factor()?;
+        loop {
+            if let Some(Elem::Div) = self.peek() {
+                self.next();
+                let start = self.tokens.get(self.index).map_or(self.end.start, |token| token.span.start);
+                let (factor, factor_kind) = self.parse_factor()?;
+                let span = Span::new(start, self.tokens[self.index - 1].span.end);
+                expression = Expression::Div(Box::new(expression), Box::new(factor), span);
+                kind = factor_kind;
+                continue
+            }
+            // Juxtaposition is a product, except for two numbers side by side
+            let is_product = match self.peek() {
+                Some(Elem::Prod) => {
+                    self.next();
+                    true
+                },
+                Some(Elem::LeftParen | Elem::X | Elem::Constant(_) | Elem::Function(_)) => true,
+                Some(Elem::NumInt(_) | Elem::NumBig(_) | Elem::NumFloat(_) | Elem::NumFraction(_) | Elem::NumBigFraction(_)) => {
+                    kind != FactorKind::Number
+                },
+                _ => false,
+            };
+            if !is_product {
+                return Ok(expression);
+            }
+            let (factor, factor_kind) = self.parse_factor()?;
+            let span = Span::new(start, self.tokens[self.index - 1].span.end);
+            expression = Expression::Mul(Box::new(expression), Box::new(factor), span);
+            kind = factor_kind;
+        }
+    }
+
+    fn parse_group(&mut self, open: &Token) -> Result<Expression, ParseError> {
+        // The left parenthesis has been read already
+        self.depth += 1;
+        let expression = self.parse_sum()?;
+        match self.next() {
+            Some(Token {elem: Elem::RightParen, ..}) => {},
+            Some(other) => return Err(unexpected_token(other)),
+            None => return Err(ParseError::new(ErrorKind::UnbalancedParentheses, open.span)),
+        }
+        self.depth -= 1;
+        Ok(expression)
+    }
+
+    fn parse_factor(&mut self) -> Result<(Expression, FactorKind), ParseError> {
+        let token = match self.next() {
+            Some(token) => token,
+            None => return Err(ParseError::new(ErrorKind::MissingTerm, self.end)),
+        };
+        let (expression, kind) = match &token.elem {
+            Elem::NumInt(n) => (Expression::Value(Coefficient::NumInt(*n)), FactorKind::Number),
+            Elem::NumBig(n) => (Expression::Value(Coefficient::NumBig(n.clone())), FactorKind::Number),
+            Elem::NumFloat(n) => (Expression::Value(Coefficient::NumFloat(*n)), FactorKind::Number),
+            Elem::NumFraction(f) => (Expression::Value(Coefficient::from_fraction(*f)), FactorKind::Number),
+            Elem::NumBigFraction(f) => (Expression::Value(Coefficient::from_big_fraction(f.clone())), FactorKind::Number),
+            Elem::X => (Expression::Variable, FactorKind::Variable),
+            Elem::LeftParen => (self.parse_group(token)?, FactorKind::Group),
+            // A constant is not a number typed by the user, so it can be raised to a power
+            Elem::Constant(constant) => (Expression::Value(constant.value()), FactorKind::Group),
+            Elem::Function(function) => {
+                let argument = match self.next() {
+                    Some(open @ Token {elem: Elem::LeftParen, ..}) => self.parse_group(open)?,
+                    Some(other) => return Err(unexpected_token(other)),
+                    None => return Err(ParseError::new(ErrorKind::MissingTerm, self.end)),
+                };
+                let span = Span::new(token.span.start, self.tokens[self.index - 1].span.end);
+                (Expression::Function(*function, Box::new(argument), span), FactorKind::Group)
+            },
+            Elem::RightParen if self.depth > 0 => {
+                return Err(ParseError::new(ErrorKind::MissingTerm, token.span))
+            },
+            _ => return Err(unexpected_token(token)),
+        };
+        if let Some(Elem::Power) = self.peek() {
+            let power = self.next().unwrap();
+            if kind == FactorKind::Number {
+                return Err(ParseError::new(ErrorKind::MisplacedOperator, power.span))
+            }
+            let is_minus = self.peek() == Some(&Elem::Minus);
+            if is_minus {
+                self.next();
+            }
+            return match self.next() {
+                // e^-n is 1 / e^n, which only a single term can give
+                Some(Token {elem: Elem::NumInt(n), span}) if is_minus => {
+                    let span = Span::new(token.span.start, span.end);
+                    let one = Expression::Value(Coefficient::NumInt(1));
+                    let divisor = Expression::Power(Box::new(expression), *n, span);
+                    Ok((Expression::Div(Box::new(one), Box::new(divisor), span), kind))
+                },
+                Some(Token {elem: Elem::NumInt(n), span}) => {
+                    Ok((Expression::Power(Box::new(expression), *n, Span::new(token.span.start, span.end)), kind))
+                },
+                Some(other) => Err(unexpected_token(other)),
+                None => Err(ParseError::new(ErrorKind::MissingTerm, self.end)),
+            }
+        }
+        Ok((expression, kind))
+    }
+}
+
+
+pub fn parse_expression(tokens: &[Token], end: Span) -> Result<Expression, ParseError> {
+    // end is where a missing term is reported, the equal sign or the end of the input
+    let mut parser = Parser {tokens, index: 0, depth: 0, end};
+    let expression = parser.parse_sum()?;
+    match parser.next() {
+        None => Ok(expression),
+        Some(token) => Err(unexpected_token(token)),
+    }
+}
+
+
+fn combine_terms(terms: &[Term]) -> Vec<Term> {
+    hash_terms_to_sorted_vec(reduce_equation(terms, &[]))
+}
+
+
+fn checked_degree(degree: Option<i64>, span: Span) -> Result<i64, ParseError> {
+    // Expanding stops as soon as a degree leaves the range the solver supports
+    match degree {
+        Some(degree) if degree.abs() <= MAX_DEGREE => Ok(degree),
+        _ => Err(ParseError::new(ErrorKind::DegreeTooLarge, span)),
+    }
+}
+
+
+fn has_fractions(terms: &[Term]) -> bool {
+    terms.iter().any(|term| matches!(term.coefficient, Coefficient::NumFraction(_) | Coefficient::NumBigFraction(_)))
+}
+
+
+fn scale_terms(terms: &[Term], factor: &Coefficient) -> Vec<Term> {
+    terms.iter().map(|term| Term {coefficient: term.coefficient.mul(factor), degree: term.degree}).collect()
+}
+
+
+fn div_terms_by(terms: Vec<Term>, divisor: &Coefficient) -> Vec<Term> {
+    // divisor must not be zero
+    terms.into_iter().map(|term| Term {coefficient: term.coefficient.div(divisor).unwrap(), degree: term.degree}).collect()
+}
+
+
+fn mul_terms(lhs: &[Term], rhs: &[Term], span: Span) -> Result<Vec<Term>, ParseError> {
+    // Fractions are multiplied as integers over a common bottom, so that each coefficient is reduced once
+    if has_fractions(lhs) || has_fractions(rhs) {
+        if let Some((lhs_bottom, rhs_bottom)) = lcm_of_bottoms(lhs).zip(lcm_of_bottoms(rhs)) {
+            let lhs = scale_terms(lhs, &Coefficient::from_big_int(lhs_bottom.clone()));
+            let rhs = scale_terms(rhs, &Coefficient::from_big_int(rhs_bottom.clone()));
+            let bottom = Coefficient::from_big_int(lhs_bottom.mul(&rhs_bottom));
+            return Ok(div_terms_by(mul_terms(&lhs, &rhs, span)?, &bottom))
+        }
+    }
+    let mut vec = Vec::new();
+    for left in lhs {
+        for right in rhs {
+            vec.push(Term {
+                coefficient: left.coefficient.mul(&right.coefficient),
+                degree: checked_degree(left.degree.checked_add(right.degree), span)?,
+            });
+        }
+    }
+    Ok(combine_terms(&vec))
+}
+
+
+fn coefficient_bits(terms: &[Term]) -> usize {
+    // The size of the largest top or bottom, floats count as nothing since their size is fixed
+    let bits = |term: &Term| term.coefficient.to_big_fraction().map_or(0, |f| f.top().bits().max(f.bottom().bits()));
+    terms.iter().map(bits).max().unwrap_or(0)
+}
+
+
+fn check_power(base: &[Term], n: i64, span: Span) -> Result<(), ParseError> {
+    // The exponent is bounded like the degrees, and the size of the coefficients by the sum of len^n products
+    // of n coefficients that each of them is, so that a power too slow to expand is refused before starting
+    if n > MAX_DEGREE {
+        return Err(ParseError::new(ErrorKind::DegreeTooLarge, span))
+    }
+    let sum_bits = (usize::BITS - base.len().saturating_sub(1).leading_zeros()) as usize;
+    if (coefficient_bits(base) + sum_bits).saturating_mul(n.max(0) as usize) > MAX_COEFFICIENT_BITS {
+        return Err(ParseError::new(ErrorKind::ExpansionTooLarge, span))
+    }
+    Ok(())
+}
+
+
+fn power_terms(base: &[Term], n: i64, span: Span) -> Result<Vec<Term>, ParseError> {
+    // Exponentiation by squaring
+    check_power(base, n, span)?;
+    // Likewise a base with fractions is raised as integers, over the power of their common bottom
+    if has_fractions(base) {
+        if let Some(bottom) = lcm_of_bottoms(base) {
+            let bottom = Coefficient::from_big_int(bottom);
+            let mut divisor = Coefficient::NumInt(1);
+            for _ in 0..n {
+                divisor = divisor.mul(&bottom);
+            }
+            return Ok(div_terms_by(power_terms(&scale_terms(base, &bottom), n, span)?, &divisor))
+        }
+    }
+    let mut vec = vec![Term {coefficient: Coefficient::NumInt(1), degree: 0}];
+    let mut square = base.to_vec();
+    let mut n = n;
+    while n > 0 {
+        if n % 2 == 1 {
+            vec = mul_terms(&vec, &square, span)?;
+        }
+        n /= 2;
+        if n > 0 {
+            square = mul_terms(&square, &square, span)?;
+        }
+    }
+    Ok(vec)
+}
+
+
+fn div_terms(lhs: &[Term], rhs: &[Term], span: Span) -> Result<Vec<Term>, ParseError> {
+    // Only a single term divides a polynomial into a polynomial, with negative degrees for X
+    let divisor = match rhs {
+        [] => return Err(ParseError::new(ErrorKind::DivisionByZero, span)),
+        [divisor] => divisor,
+        _ => return Err(ParseError::new(ErrorKind::PolynomialDivisor, span)),
+    };
+    let mut vec = Vec::new();
+    for term in lhs {
+        let coefficient = match term.coefficient.div(&divisor.coefficient) {
+            Some(v) => v,
+            None => return Err(ParseError::new(ErrorKind::DivisionByZero, span)),
+        };
+        vec.push(Term {coefficient, degree: checked_degree(term.degree.checked_sub(divisor.degree), span)?});
+    }
+    Ok(vec)
+}
+
+
+fn neg_terms(terms: &[Term]) -> Vec<Term> {
+    terms.iter().map(|term| Term {
+        coefficient: term.coefficient.mul_minus(),
+        degree: term.degree,
+    }).collect()
+}
+
+
+pub fn expand_expression(expression: &Expression) -> Result<Vec<Term>, ParseError> {
+    Ok(match expression {
+        Expression::Value(coefficient) => vec![Term {coefficient: coefficient.clone(), degree: 0}],
+        Expression::Variable => vec![Term {coefficient: Coefficient::NumInt(1), degree: 1}],
+        Expression::Neg(e) => neg_terms(&expand_expression(e)?),
+        Expression::Add(lhs, rhs) => {
+            let mut vec = expand_expression(lhs)?;
+            vec.extend(expand_expression(rhs)?);
+            combine_terms(&vec)
+        },
+        Expression::Sub(lhs, rhs) => {
+            let mut vec = expand_expression(lhs)?;
+            vec.extend(neg_terms(&expand_expression(rhs)?));
+            combine_terms(&vec)
+        },
+        Expression::Mul(lhs, rhs, span) => mul_terms(&expand_expression(lhs)?, &expand_expression(rhs)?, *span)?,
+        Expression::Div(lhs, rhs, span) => {
+            // Zero terms left over by the expansion do not count in the divisor
+            let divisor: Vec<Term> = expand_expression(rhs)?.into_iter().filter(|term| !term.coefficient.is_zero()).collect();
+            div_terms(&expand_expression(lhs)?, &divisor, *span)?
+        },
+        Expression::Power(e, n, span) => power_terms(&expand_expression(e)?, *n, *span)?,
+        Expression::Function(function, e, span) => {
+            let argument = expand_expression(e)?.into_iter().filter(|term| !term.coefficient.is_zero()).collect::<Vec<Term>>();
+            let value = match argument.as_slice() {
+                [] => Coefficient::NumInt(0),
+                [Term {coefficient, degree: 0}] => coefficient.clone(),
+                _ => return Err(ParseError::new(ErrorKind::VariableInFunction, *span)),
+            };
+            match function.apply(&value) {
+                Some(coefficient) => vec![Term {coefficient, degree: 0}],
+                None => return Err(ParseError::new(ErrorKind::NegativeSquareRoot, *span)),
+            }
+        },
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fraction::Fraction;
+
+    fn tokens(elems: Vec<Elem>) -> Vec<Token> {
+        elems.into_iter().enumerate().map(|(i, elem)| Token::new(elem, i, i + 1)).collect()
+    }
+
+    fn parse_elems(elems: Vec<Elem>) -> Result<Expression, ParseError> {
+        let tokens = tokens(elems);
+        let end = Span::new(tokens.len(), tokens.len() + 1);
+        parse_expression(&tokens, end)
+    }
+
+    #[test]
+    fn parse_expression_flat() {
+        let elems = vec![Elem::NumInt(2), Elem::X, Elem::Plus, Elem::NumInt(1)];
+        assert_eq!(parse_elems(elems), Ok(Expression::Add(
+            Box::new(Expression::Mul(
+                Box::new(Expression::Value(Coefficient::NumInt(2))),
+                Box::new(Expression::Variable),
+                Span::new(0, 2))),
+            Box::new(Expression::Value(Coefficient::NumInt(1))))));
+    }
+
+    #[test]
+    fn parse_expression_group_power() {
+        let elems = vec![Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen, Elem::Power, Elem::NumInt(2)];
+        assert_eq!(parse_elems(elems), Ok(Expression::Power(
+            Box::new(Expression::Add(
+                Box::new(Expression::Variable),
+                Box::new(Expression::Value(Coefficient::NumInt(1))))),
+            2,
+            Span::new(0, 7))));
+    }
+
+    #[test]
+    fn parse_expression_error_unclosed() {
+        let elems = vec![Elem::LeftParen, Elem::X];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::UnbalancedParentheses, Span::new(0, 1))));
+    }
+
+    #[test]
+    fn parse_expression_error_unopened() {
+        let elems = vec![Elem::X, Elem::RightParen];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::UnbalancedParentheses, Span::new(1, 2))));
+    }
+
+    #[test]
+    fn parse_expression_error_empty_group() {
+        let elems = vec![Elem::LeftParen, Elem::RightParen];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::MissingTerm, Span::new(1, 2))));
+    }
+
+    #[test]
+    fn parse_expression_error_number_power() {
+        let elems = vec![Elem::NumInt(2), Elem::Power, Elem::NumInt(2)];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(1, 2))));
+    }
+
+    #[test]
+    fn parse_expression_error_trailing_operator() {
+        let elems = vec![Elem::LeftParen, Elem::X, Elem::RightParen, Elem::Plus];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::MissingTerm, Span::new(4, 5))));
+    }
+
+    #[test]
+    fn expand_expression_juxtaposition() {
+        let elems = vec![Elem::X, Elem::X, Elem::NumInt(2), Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumInt(2), degree: 2},
+            Term {coefficient: Coefficient::NumInt(2), degree: 3},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_div() {
+        let elems = vec![
+            Elem::LeftParen, Elem::NumInt(3), Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen,
+            Elem::Div, Elem::LeftParen, Elem::NumInt(2), Elem::X, Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap()), degree: -1},
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(3, 2).unwrap()), degree: 0},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_negative_power() {
+        let elems = vec![
+            Elem::NumInt(3), Elem::LeftParen, Elem::NumInt(2), Elem::X, Elem::RightParen, Elem::Power, Elem::Minus, Elem::NumInt(2)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(3, 4).unwrap()), degree: -2},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_error_negative_power_of_sum() {
+        let elems = vec![Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen, Elem::Power, Elem::Minus, Elem::NumInt(1)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::PolynomialDivisor, Span::new(0, 8))));
+    }
+
+    #[test]
+    fn expand_expression_error_polynomial_divisor() {
+        let elems = vec![Elem::NumInt(1), Elem::Div, Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::PolynomialDivisor, Span::new(2, 7))));
+    }
+
+    #[test]
+    fn parse_expression_error_two_numbers() {
+        let elems = vec![Elem::LeftParen, Elem::NumInt(2), Elem::NumInt(3), Elem::RightParen];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::UnexpectedValue, Span::new(2, 3))));
+    }
+
+    #[test]
+    fn expand_expression_square() {
+        let elems = vec![Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen, Elem::Power, Elem::NumInt(2)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(2), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_neg_group() {
+        let elems = vec![Elem::Minus, Elem::NumInt(3), Elem::LeftParen, Elem::X, Elem::Minus, Elem::NumFloat(0.5), Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumFloat(1.5), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 1},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_two_groups() {
+        let elems = vec![
+            Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(1), Elem::RightParen,
+            Elem::LeftParen, Elem::X, Elem::Minus, Elem::NumInt(1), Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumInt(-1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_function() {
+        let elems = vec![
+            Elem::Function(Function::Sqrt), Elem::LeftParen, Elem::NumInt(8), Elem::Div, Elem::NumInt(18), Elem::RightParen, Elem::X];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(2, 3).unwrap()), degree: 1},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_constant_power() {
+        let elems = vec![Elem::NumInt(2), Elem::Constant(crate::term::Constant::Pi), Elem::Power, Elem::NumInt(2)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![
+            Term {coefficient: Coefficient::NumFloat(2.0 * std::f64::consts::PI * std::f64::consts::PI), degree: 0},
+        ]));
+    }
+
+    #[test]
+    fn expand_expression_error_variable_in_function() {
+        let elems = vec![Elem::NumInt(1), Elem::Plus, Elem::Function(Function::Abs), Elem::LeftParen, Elem::X, Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::VariableInFunction, Span::new(2, 6))));
+    }
+
+    #[test]
+    fn expand_expression_error_negative_square_root() {
+        let elems = vec![Elem::Function(Function::Sqrt), Elem::LeftParen, Elem::Minus, Elem::NumInt(2), Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::NegativeSquareRoot, Span::new(0, 5))));
+    }
+
+    #[test]
+    fn parse_expression_error_function_without_parentheses() {
+        let elems = vec![Elem::Function(Function::Sqrt), Elem::NumInt(2)];
+        assert_eq!(parse_elems(elems), Err(ParseError::new(ErrorKind::UnexpectedValue, Span::new(1, 2))));
+    }
+
+    #[test]
+    fn expand_expression_error_exponent_too_large() {
+        let elems = vec![Elem::LeftParen, Elem::X, Elem::RightParen, Elem::Power, Elem::NumInt(i64::MAX)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 5))));
+    }
+
+    #[test]
+    fn expand_expression_error_product_too_large() {
+        let elems = vec![
+            Elem::LeftParen, Elem::X, Elem::Power, Elem::NumInt(600), Elem::RightParen,
+            Elem::LeftParen, Elem::X, Elem::Power, Elem::NumInt(600), Elem::RightParen];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 10))));
+    }
+
+    #[test]
+    fn expand_expression_error_coefficients_too_large() {
+        // (X + 99999999999999999)^1000 would have coefficients of about 57000 bits
+        let elems = vec![
+            Elem::LeftParen, Elem::X, Elem::Plus, Elem::NumInt(99999999999999999), Elem::RightParen, Elem::Power, Elem::NumInt(1000)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Err(ParseError::new(ErrorKind::ExpansionTooLarge, Span::new(0, 7))));
+    }
+
+    #[test]
+    fn check_power_fraction_in_budget() {
+        // (X + 1/3)^1000 has coefficients of about 1600 bits, which is expanded
+        let base = vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 3).unwrap()), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 1},
+        ];
+        assert_eq!(check_power(&base, 1000, Span::new(0, 1)), Ok(()));
+    }
+
+    #[test]
+    fn expand_expression_power_by_squaring() {
+        let elems = vec![Elem::LeftParen, Elem::NumInt(2), Elem::X, Elem::RightParen, Elem::Power, Elem::NumInt(13)];
+        let expression = parse_elems(elems).unwrap();
+        assert_eq!(expand_expression(&expression), Ok(vec![Term {coefficient: Coefficient::NumInt(8192), degree: 13}]));
+    }
+}
@@ -1,9 +1,11 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops;
 
 use crate::math_utility::math_utility::checked_gcd;
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy)]
 pub struct Fraction {
     top: i64,
     bottom: i64,
@@ -16,14 +18,8 @@ impl Fraction {
             None
         } else {
             if bottom < 0 {
-                let neg_top = match top.checked_neg() {
-                    Some(n) => n,
-                    None => return None,
-                };
-                let neg_bottom = match bottom.checked_neg() {
-                    Some(n) => n,
-                    None => return None,
-                };
+                let neg_top = top.checked_neg()?;
+                let neg_bottom = bottom.checked_neg()?;
                 if neg_top == i64::MIN {
                     return None
                 }
@@ -39,12 +35,168 @@ impl Fraction {
 
 
     pub fn make_irreducible_fraction(&self) -> Fraction {
-        let gcd_value = match checked_gcd(self.top, self.bottom) {
-            Some(v) => v,
-            None => 1,
-        };
+        let gcd_value = checked_gcd(self.top, self.bottom).unwrap_or(1);
         Fraction {top: self.top / gcd_value, bottom: self.bottom / gcd_value}
     }
+
+    pub fn top(&self) -> i64 {
+        self.top
+    }
+
+    pub fn bottom(&self) -> i64 {
+        self.bottom
+    }
+
+    pub fn checked_add(&self, other: &Fraction) -> Option<Fraction> {
+        let top = self.top.checked_mul(other.bottom)?
+            .checked_add(other.top.checked_mul(self.bottom)?)?;
+        let bottom = self.bottom.checked_mul(other.bottom)?;
+        Some(Fraction::safe_new(top, bottom)?.make_irreducible_fraction())
+    }
+
+    pub fn checked_mul(&self, other: &Fraction) -> Option<Fraction> {
+        let top = self.top.checked_mul(other.top)?;
+        let bottom = self.bottom.checked_mul(other.bottom)?;
+        Some(Fraction::safe_new(top, bottom)?.make_irreducible_fraction())
+    }
+
+    pub fn checked_sub(&self, other: &Fraction) -> Option<Fraction> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    pub fn checked_div(&self, other: &Fraction) -> Option<Fraction> {
+        self.checked_mul(&Fraction::safe_new(other.bottom, other.top)?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Fraction> {
+        Fraction::safe_new(self.top.checked_neg()?, self.bottom)
+    }
+
+    pub fn from_float(value: f64) -> Option<Fraction> {
+        // A finite f64 is exactly mantissa * 2^exponent, kept when both parts fit in i64
+        if !value.is_finite() {
+            return None
+        }
+        let bits = value.to_bits();
+        let exponent_bits = ((bits >> 52) & 0x7ff) as i64;
+        let mut mantissa = (bits & 0xf_ffff_ffff_ffff) as i64;
+        let mut exponent = if exponent_bits == 0 {
+            -1074
+        } else {
+            mantissa |= 1 << 52;
+            exponent_bits - 1075
+        };
+        if mantissa == 0 {
+            return Some(Fraction {top: 0, bottom: 1})
+        }
+        while mantissa % 2 == 0 && exponent < 0 {
+            mantissa /= 2;
+            exponent += 1;
+        }
+        let top = if value < 0.0 {-mantissa} else {mantissa};
+        if exponent >= 0 {
+            Fraction::safe_new(top.checked_mul(2_i64.checked_pow(exponent as u32)?)?, 1)
+        } else {
+            Fraction::safe_new(top, 2_i64.checked_pow((-exponent) as u32)?)
+        }
+    }
+
+    pub fn to_float(self) -> f64 {
+        self.top as f64 / self.bottom as f64
+    }
+
+    pub fn to_decimal_string(self) -> Option<String> {
+        // Only a bottom of the form 2^a * 5^b gives a finite decimal expansion
+        let mut bottom = self.bottom;
+        let mut counts = [0_u32; 2];
+        for (i, factor) in [2, 5].into_iter().enumerate() {
+            while bottom % factor == 0 {
+                bottom /= factor;
+                counts[i] += 1;
+            }
+        }
+        if bottom != 1 {
+            return None
+        }
+        let places = counts[0].max(counts[1]);
+        let scale = 2_i64.checked_pow(places - counts[0])?.checked_mul(5_i64.checked_pow(places - counts[1])?)?;
+        let mut top = self.top.checked_mul(scale)?.unsigned_abs();
+        let mut digits = String::new();
+        for _ in 0..places {
+            digits.insert(0, char::from(b'0' + (top % 10) as u8));
+            top /= 10;
+        }
+        let sign = if self.top < 0 {"-"} else {""};
+        if places == 0 {
+            Some(format!("{}{}", sign, top))
+        } else {
+            Some(format!("{}{}.{}", sign, top, digits))
+        }
+    }
+}
+
+
+impl PartialEq for Fraction {
+    fn eq(&self, other: &Fraction) -> bool {
+        // 2 / 4 equals 1 / 2, as the order below says
+        self.top as i128 * other.bottom as i128 == other.top as i128 * self.bottom as i128
+    }
+}
+
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Fraction) -> Option<Ordering> {
+        // Bottoms are always positive, so cross multiplication keeps the order
+        let lhs = self.top as i128 * other.bottom as i128;
+        let rhs = other.top as i128 * self.bottom as i128;
+        lhs.partial_cmp(&rhs)
+    }
+}
+
+
+// Like the integer operators, these panic on overflow, use the checked methods otherwise
+impl ops::Add for Fraction {
+    type Output = Fraction;
+
+    fn add(self, other: Fraction) -> Fraction {
+        self.checked_add(&other).expect("attempt to add with overflow")
+    }
+}
+
+
+impl ops::Sub for Fraction {
+    type Output = Fraction;
+
+    fn sub(self, other: Fraction) -> Fraction {
+        self.checked_sub(&other).expect("attempt to subtract with overflow")
+    }
+}
+
+
+impl ops::Mul for Fraction {
+    type Output = Fraction;
+
+    fn mul(self, other: Fraction) -> Fraction {
+        self.checked_mul(&other).expect("attempt to multiply with overflow")
+    }
+}
+
+
+impl ops::Div for Fraction {
+    type Output = Fraction;
+
+    fn div(self, other: Fraction) -> Fraction {
+        self.checked_div(&other).expect("attempt to divide by zero or with overflow")
+    }
+}
+
+
+impl ops::Neg for Fraction {
+    type Output = Fraction;
+
+    fn neg(self) -> Fraction {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
 }
 
 
@@ -74,52 +226,49 @@ mod tests {
     fn safe_new_normal() {
         let top = 2;
         let bottom = 1;
-        assert_eq!(Fraction::safe_new(top, bottom),
-            Some(Fraction {top: 2, bottom: 1}));
+        assert_eq!(Fraction::safe_new(top, bottom).map(|f| (f.top, f.bottom)), Some((2, 1)));
     }
 
     #[test]
     fn safe_new_top_plus_bottom_minus() {
         let top = 2;
         let bottom = -1;
-        assert_eq!(Fraction::safe_new(top, bottom),
-            Some(Fraction {top: -2, bottom: 1}));
+        assert_eq!(Fraction::safe_new(top, bottom).map(|f| (f.top, f.bottom)), Some((-2, 1)));
     }
 
     #[test]
     fn safe_new_top_minus_bottom_minus() {
         let top = -2;
         let bottom = -1;
-        assert_eq!(Fraction::safe_new(top, bottom),
-            Some(Fraction {top: 2, bottom: 1}));
+        assert_eq!(Fraction::safe_new(top, bottom).map(|f| (f.top, f.bottom)), Some((2, 1)));
     }
 
     #[test]
     fn make_irreducible_fraction_2_4() {
         let fraction = Fraction {top: 2, bottom: 4};
-        assert_eq!(fraction.make_irreducible_fraction(), 
-            Fraction {top: 1, bottom: 2});
+        let fraction = fraction.make_irreducible_fraction();
+        assert_eq!((fraction.top, fraction.bottom), (1, 2));
     }
 
     #[test]
     fn make_irreducible_fraction_1_2() {
         let fraction = Fraction {top: 1, bottom: 2};
-        assert_eq!(fraction.make_irreducible_fraction(), 
-            Fraction {top: 1, bottom: 2});
+        let fraction = fraction.make_irreducible_fraction();
+        assert_eq!((fraction.top, fraction.bottom), (1, 2));
     }
 
     #[test]
     fn make_irreducible_fraction_minus_1_2() {
         let fraction = Fraction {top: -1, bottom: 2};
-        assert_eq!(fraction.make_irreducible_fraction(), 
-            Fraction {top: -1, bottom: 2});
+        let fraction = fraction.make_irreducible_fraction();
+        assert_eq!((fraction.top, fraction.bottom), (-1, 2));
     }
 
     #[test]
     fn make_irreducible_fraction_0_2() {
         let fraction = Fraction {top: 0, bottom: 2};
-        assert_eq!(fraction.make_irreducible_fraction(), 
-            Fraction {top: 0, bottom: 1});
+        let fraction = fraction.make_irreducible_fraction();
+        assert_eq!((fraction.top, fraction.bottom), (0, 1));
     }
 
     #[test]
@@ -139,4 +288,144 @@ mod tests {
         let fraction = Fraction {top: 2, bottom: 1};
         assert_eq!(format!("{}", fraction), "2".to_string());
     }
+
+    #[test]
+    fn checked_add_normal() {
+        let lhs = Fraction {top: 1, bottom: 2};
+        let rhs = Fraction {top: 1, bottom: 3};
+        assert_eq!(lhs.checked_add(&rhs), Some(Fraction {top: 5, bottom: 6}));
+    }
+
+    #[test]
+    fn checked_add_irreducible() {
+        let lhs = Fraction {top: 1, bottom: 6};
+        let rhs = Fraction {top: 1, bottom: 3};
+        assert_eq!(lhs.checked_add(&rhs).map(|f| (f.top, f.bottom)), Some((1, 2)));
+    }
+
+    #[test]
+    fn checked_add_overflow() {
+        let lhs = Fraction {top: i64::MAX, bottom: 1};
+        let rhs = Fraction {top: 1, bottom: 1};
+        assert_eq!(lhs.checked_add(&rhs), None);
+    }
+
+    #[test]
+    fn checked_mul_normal() {
+        let lhs = Fraction {top: -2, bottom: 3};
+        let rhs = Fraction {top: 3, bottom: 4};
+        assert_eq!(lhs.checked_mul(&rhs), Some(Fraction {top: -1, bottom: 2}));
+    }
+
+    #[test]
+    fn checked_neg_normal() {
+        let fraction = Fraction {top: 1, bottom: 2};
+        assert_eq!(fraction.checked_neg(), Some(Fraction {top: -1, bottom: 2}));
+    }
+
+    #[test]
+    fn to_float_half() {
+        let fraction = Fraction {top: 1, bottom: 2};
+        assert_eq!(fraction.to_float(), 0.5);
+    }
+
+    #[test]
+    fn to_decimal_string_93_10() {
+        let fraction = Fraction {top: -93, bottom: 10};
+        assert_eq!(fraction.to_decimal_string(), Some("-9.3".to_string()));
+    }
+
+    #[test]
+    fn to_decimal_string_1_8() {
+        let fraction = Fraction {top: 1, bottom: 8};
+        assert_eq!(fraction.to_decimal_string(), Some("0.125".to_string()));
+    }
+
+    #[test]
+    fn to_decimal_string_1_3() {
+        let fraction = Fraction {top: 1, bottom: 3};
+        assert_eq!(fraction.to_decimal_string(), None);
+    }
+
+    #[test]
+    fn checked_sub_normal() {
+        let lhs = Fraction {top: 1, bottom: 2};
+        let rhs = Fraction {top: 1, bottom: 3};
+        assert_eq!(lhs.checked_sub(&rhs), Some(Fraction {top: 1, bottom: 6}));
+    }
+
+    #[test]
+    fn checked_div_normal() {
+        let lhs = Fraction {top: 1, bottom: 2};
+        let rhs = Fraction {top: -3, bottom: 4};
+        assert_eq!(lhs.checked_div(&rhs), Some(Fraction {top: -2, bottom: 3}));
+    }
+
+    #[test]
+    fn checked_div_zero() {
+        let lhs = Fraction {top: 1, bottom: 2};
+        let rhs = Fraction {top: 0, bottom: 1};
+        assert_eq!(lhs.checked_div(&rhs), None);
+    }
+
+    #[test]
+    fn eq_same_as_partial_cmp() {
+        let lhs = Fraction {top: 2, bottom: 4};
+        let rhs = Fraction {top: 1, bottom: 2};
+        assert_eq!(lhs, rhs);
+        assert_eq!(lhs.partial_cmp(&rhs), Some(Ordering::Equal));
+        assert_ne!(lhs, Fraction {top: 1, bottom: 3});
+    }
+
+    #[test]
+    fn partial_cmp_minus() {
+        let lhs = Fraction {top: -1, bottom: 2};
+        let rhs = Fraction {top: -1, bottom: 3};
+        assert!(lhs < rhs);
+    }
+
+    #[test]
+    fn partial_cmp_large() {
+        let lhs = Fraction {top: i64::MAX, bottom: i64::MAX - 1};
+        let rhs = Fraction {top: i64::MAX - 1, bottom: i64::MAX - 2};
+        assert!(lhs < rhs);
+    }
+
+    #[test]
+    fn ops_expression() {
+        let half = Fraction {top: 1, bottom: 2};
+        let third = Fraction {top: 1, bottom: 3};
+        assert_eq!(-(half + third) * half / third - half, Fraction {top: -7, bottom: 4});
+    }
+
+    #[test]
+    #[should_panic]
+    fn ops_div_zero() {
+        let _ = Fraction {top: 1, bottom: 2} / Fraction {top: 0, bottom: 1};
+    }
+
+    #[test]
+    fn from_float_decimal() {
+        assert_eq!(Fraction::from_float(-0.375), Some(Fraction {top: -3, bottom: 8}));
+    }
+
+    #[test]
+    fn from_float_integer() {
+        assert_eq!(Fraction::from_float(1024.0), Some(Fraction {top: 1024, bottom: 1}));
+    }
+
+    #[test]
+    fn from_float_tenth() {
+        assert_eq!(Fraction::from_float(0.1), Some(Fraction {top: 3602879701896397, bottom: 36028797018963968}));
+    }
+
+    #[test]
+    fn from_float_too_small() {
+        assert_eq!(Fraction::from_float(1e-30), None);
+    }
+
+    #[test]
+    fn from_float_nan() {
+        assert_eq!(Fraction::from_float(f64::NAN), None);
+    }
 }
@@ -0,0 +1,340 @@
+pub mod term;
+pub mod error;
+pub mod fraction;
+pub mod big_int;
+pub mod big_fraction;
+mod parse_string;
+mod elem_to_term;
+mod expression;
+mod reduce_equation;
+mod utility;
+mod solution;
+mod solution_string;
+mod solution_json;
+mod solution_latex;
+mod steps;
+mod math_utility;
+pub mod complex;
+mod durand_kerner;
+
+use parse_string::{parse_string, find_variable};
+use elem_to_term::{elem_to_term, divides_by_variable};
+use reduce_equation::reduce_equation;
+use solution::{is_degree_supported, clear_negative_degrees};
+use utility::{hash_terms_to_sorted_vec, make_reduced_form_string, make_natural_form_string, evaluate_degree_of_terms};
+
+pub use term::{Term, Coefficient};
+pub use solution::{Solution, Root, Exact};
+pub use solution_string::{make_solution_string, make_variable_solution_string, make_excluded_zero_string, make_root_string};
+pub use solution_json::{make_solution_json, make_error_json};
+pub use solution_latex::{make_reduced_form_latex, make_solution_latex};
+pub use steps::make_steps_string;
+pub use error::{ErrorKind, ParseError, Span};
+pub use utility::make_error_string;
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polynomial {
+    terms: Vec<Term>,
+    // The variable as it was written in the equation
+    variable: char,
+    // An equation that divides by X is undefined at X = 0
+    excludes_zero: bool,
+}
+
+
+impl Polynomial {
+    pub fn new(left_terms: &[Term], right_terms: &[Term], variable: char) -> Polynomial {
+        // left = right is reduced to a single polynomial equal to zero
+        let mut terms = hash_terms_to_sorted_vec(reduce_equation(left_terms, right_terms));
+        let excludes_zero = left_terms.iter().chain(right_terms).any(|term| term.degree < 0);
+        // 1/X = 2 is kept as 1 = 2X, so that its degree and forms describe the equation that is solved.
+        // A degree out of range is left for reduce to refuse, since clearing it could overflow
+        if is_degree_supported(&terms) {
+            if let Some(cleared) = clear_negative_degrees(&terms) {
+                terms = cleared;
+            }
+        }
+        Polynomial {terms, variable, excludes_zero}
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    pub fn variable(&self) -> char {
+        self.variable
+    }
+
+    pub fn excludes_zero(&self) -> bool {
+        self.excludes_zero
+    }
+
+    pub fn degree(&self) -> i64 {
+        evaluate_degree_of_terms(&self.terms)
+    }
+
+    pub fn reduced_form(&self) -> String {
+        make_reduced_form_string(&self.terms, self.variable)
+    }
+
+    pub fn natural_form(&self, is_superscript: bool) -> String {
+        // X² + 2X + 1 = 0 instead of 1 * X^0 + 2 * X^1 + 1 * X^2 = 0
+        make_natural_form_string(&self.terms, is_superscript, self.variable)
+    }
+}
+
+
+pub fn parse_equation(equation: &str) -> Result<(Vec<Term>, Vec<Term>), ParseError> {
+    elem_to_term(parse_string(equation)?)
+}
+
+
+pub fn reduce(equation: &str) -> Result<Polynomial, ParseError> {
+    let tokens = parse_string(equation)?;
+    let is_divided = divides_by_variable(&tokens);
+    let variable = find_variable(equation, &tokens);
+    let (left_terms, right_terms) = elem_to_term(tokens)?;
+    let mut polynomial = Polynomial::new(&left_terms, &right_terms, variable);
+    polynomial.excludes_zero |= is_divided;
+    if !is_degree_supported(&polynomial.terms) {
+        return Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, equation.len())))
+    }
+    Ok(polynomial)
+}
+
+
+pub fn solve(polynomial: &Polynomial) -> Solution {
+    let solution = solution::solution(&polynomial.terms, polynomial.degree());
+    if polynomial.excludes_zero {
+        solution::exclude_zero(solution)
+    } else {
+        solution
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_equation_sides() {
+        assert_eq!(parse_equation("X = 2"), Ok((
+            vec![Term {coefficient: Coefficient::NumInt(1), degree: 1}],
+            vec![Term {coefficient: Coefficient::NumInt(2), degree: 0}],
+        )));
+    }
+
+    #[test]
+    fn parse_equation_error() {
+        assert_eq!(parse_equation("X + = 2"), Err(ParseError::new(ErrorKind::MisplacedOperator, Span::new(2, 3))));
+    }
+
+    #[test]
+    fn reduce_normal() {
+        let polynomial = reduce("5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0").unwrap();
+        assert_eq!(polynomial.reduced_form(), "4 * X^0 + 4 * X^1 - 9.3 * X^2 = 0".to_string());
+        assert_eq!(polynomial.degree(), 2);
+    }
+
+    #[test]
+    fn natural_form_normal() {
+        let polynomial = reduce("1 + 2X + X^2 = 0").unwrap();
+        assert_eq!(polynomial.natural_form(true), "X² + 2X + 1 = 0".to_string());
+    }
+
+    #[test]
+    fn reduce_division() {
+        let polynomial = reduce("X/2 + 1/3 = 0").unwrap();
+        assert_eq!(polynomial.reduced_form(), "1 / 3 * X^0 + 0.5 * X^1 = 0".to_string());
+    }
+
+    #[test]
+    fn solve_negative_degree() {
+        let polynomial = reduce("1/X = 2").unwrap();
+        assert_eq!(make_solution_string(&solve(&polynomial)), "The solution is:\n1 / 2".to_string());
+    }
+
+    #[test]
+    fn reduce_negative_degree_cleared() {
+        let polynomial = reduce("2/X^2 = 8").unwrap();
+        assert_eq!(polynomial.reduced_form(), "2 * X^0 - 8 * X^2 = 0".to_string());
+        assert_eq!(polynomial.degree(), 2);
+        assert!(polynomial.excludes_zero());
+        assert_eq!(reduce("(1/X)^2 = 4").unwrap().degree(), 2);
+    }
+
+    #[test]
+    fn solve_excluded_zero() {
+        let polynomial = reduce("X^2 + X^-1 = X + X^-1").unwrap();
+        assert!(polynomial.excludes_zero());
+        assert_eq!(make_solution_string(&solve(&polynomial)), "The solution is:\n1".to_string());
+    }
+
+    #[test]
+    fn reduce_variable() {
+        let polynomial = reduce("t^2 = 4").unwrap();
+        assert_eq!(polynomial.reduced_form(), "- 4 * t^0 + 1 * t^2 = 0".to_string());
+        assert_eq!(polynomial.variable(), 't');
+    }
+
+    #[test]
+    fn reduce_mixed_variables() {
+        assert_eq!(reduce("x^2 = y"), Err(ParseError::new(ErrorKind::MixedVariables('x', 'y'), Span::new(6, 7))));
+    }
+
+    #[test]
+    fn reduce_constants() {
+        let polynomial = reduce("sqrt(4) X^2 - abs(-1/2) = 0").unwrap();
+        assert_eq!(polynomial.reduced_form(), "- 0.5 * X^0 + 2 * X^2 = 0".to_string());
+    }
+
+    #[test]
+    fn reduce_degree_too_large() {
+        assert_eq!(reduce("X^9223372036854775807 = 1"), Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 25))));
+    }
+
+    #[test]
+    fn reduce_degree_too_large_negative() {
+        assert_eq!(reduce("X^600 = X^-600").map(|_| ()), Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 14))));
+    }
+
+    #[test]
+    fn reduce_degree_overflow() {
+        assert_eq!(reduce("X^9223372036854775807 * X^9223372036854775807 = 1").map(|_| ()),
+            Err(ParseError::new(ErrorKind::DegreeTooLarge, Span::new(0, 25))));
+    }
+
+    #[test]
+    fn solve_normal() {
+        let polynomial = reduce("X^2 = 4").unwrap();
+        assert_eq!(make_solution_string(&solve(&polynomial)), "Discriminant is strictly positive, the two solutions are:\n2\n-2".to_string());
+    }
+
+    fn roots(equation: &str) -> Vec<Root> {
+        match solve(&reduce(equation).unwrap()) {
+            Solution::Roots {roots, ..} => roots,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn solve_degree_5_large() {
+        let roots = roots("X^5 = 1e200");
+        assert_eq!(roots.len(), 5);
+        assert!(roots.iter().all(|root| (root.value.abs() / 1e40 - 1.0).abs() < 1e-12 && root.error.unwrap() < 1e26));
+    }
+
+    #[test]
+    fn solve_degree_5_large_leading() {
+        let roots = roots("1e300 X^5 = 1");
+        assert_eq!(roots.len(), 5);
+        assert!(roots.iter().all(|root| (root.value.abs() / 1e-60 - 1.0).abs() < 1e-12 && root.error.unwrap() < 1e-74));
+    }
+
+    #[test]
+    fn solve_degree_5_small() {
+        let roots = roots("X^5 = 1e-100");
+        assert_eq!(roots.len(), 5);
+        assert!(roots.iter().all(|root| (root.value.abs() / 1e-20 - 1.0).abs() < 1e-12 && root.error.unwrap() < 1e-34));
+    }
+
+    #[test]
+    fn solve_degree_5_rational_roots() {
+        let roots = roots("(X-1)^5 = 0");
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].exact, Some(Exact::Rational(big_int::BigInt::from_i64(1), big_int::BigInt::from_i64(1))));
+        assert_eq!(roots[0].multiplicity, 5);
+    }
+
+    #[test]
+    fn solve_fraction_power_exact() {
+        let roots = roots("(X+1/3)^100 = 0");
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].exact, Some(Exact::Rational(big_int::BigInt::from_i64(-1), big_int::BigInt::from_i64(3))));
+        assert_eq!(roots[0].multiplicity, 100);
+    }
+
+    #[test]
+    fn solve_fraction_sum_exact() {
+        // The bottom of the sum of 1 / p for the primes from 3 to 47 overflows i64 in the cross products
+        let roots = roots("X/3+X/7+X/11+X/13+X/17+X/19+X/23+X/29+X/31+X/37+X/41+X/43+X/47 = 1");
+        let top = big_int::BigInt::parse("61488978258849141").unwrap();
+        let bottom = big_int::BigInt::parse("59130661777482301").unwrap();
+        assert_eq!(roots[0].exact, Some(Exact::Rational(top, bottom)));
+    }
+
+    #[test]
+    fn solve_long_decimal_exact() {
+        let roots = roots("0.123456789012345678901234 X = 1");
+        let top = big_int::BigInt::parse("500000000000000000000000").unwrap();
+        let bottom = big_int::BigInt::parse("61728394506172839450617").unwrap();
+        assert_eq!(roots[0].exact, Some(Exact::Rational(top, bottom)));
+    }
+
+    fn is_close(value: f64, expected: f64) -> bool {
+        (value / expected - 1.0).abs() < 1e-12
+    }
+
+    #[test]
+    fn solve_degree_2_large_constant() {
+        // x = (-1 ± √(1 + 4e160)) / 2
+        let roots = roots("X^2 + X = 1e160");
+        assert_eq!(roots.len(), 2);
+        assert!(is_close(roots[0].value.re, 1e80 - 0.5) && is_close(roots[1].value.re, -1e80 - 0.5));
+        assert!(roots.iter().all(|root| root.error.unwrap() < 1e66));
+    }
+
+    #[test]
+    fn solve_degree_2_large_square() {
+        let roots = roots("X^2 = 2e200");
+        assert_eq!(roots.len(), 2);
+        assert!(is_close(roots[0].value.re, 1.414213562373095e100) && is_close(roots[1].value.re, -1.414213562373095e100));
+    }
+
+    #[test]
+    fn solve_degree_2_large_complex() {
+        let roots = roots("X^2 + 1e160 = 0");
+        assert_eq!(roots.len(), 2);
+        assert!(roots.iter().all(|root| root.value.re == 0.0 && is_close(root.value.im.abs(), 1e80)));
+        assert!(roots[0].value.im > 0.0 && roots[1].value.im < 0.0);
+    }
+
+    #[test]
+    fn solve_degree_4_large_biquadratic() {
+        let roots = roots("X^4 = 1e200");
+        assert_eq!(roots.len(), 4);
+        assert!(roots.iter().all(|root| is_close(root.value.abs(), 1e50)));
+        assert_eq!(roots.iter().filter(|root| root.is_real()).count(), 2);
+    }
+
+    #[test]
+    fn solve_degree_4_large_leading() {
+        // The depressed quartic is scaled to the size of its roots, about 1e-25
+        let roots = roots("1e100 X^4 + X = 1");
+        assert_eq!(roots.len(), 4);
+        assert!(roots.iter().all(|root| is_close(root.value.abs(), 1e-25)));
+    }
+
+    #[test]
+    fn solve_degree_3_large() {
+        // q^2 is beyond the range of f64 unless the depressed cubic is scaled
+        let roots = roots("X^3 = 1e160");
+        assert_eq!(roots.len(), 3);
+        assert!(is_close(roots[0].value.re, 2.154434690031884e53));
+        assert!(roots.iter().all(|root| is_close(root.value.abs(), 2.154434690031884e53)));
+    }
+
+    #[test]
+    fn solve_degree_3_small_middle_root() {
+        let roots = roots("X^3 - 3e100 X + 1 = 0");
+        assert!(is_close(roots[1].value.re, 1.0 / 3e100));
+    }
+
+    #[test]
+    fn solve_degree_4_multiplicity() {
+        let polynomial = reduce("(X - 1)^3 (X + 2) = 0").unwrap();
+        assert_eq!(make_solution_string(&solve(&polynomial)), "The two solutions are:\n1 (multiplicity 3)\n-2".to_string());
+    }
+}
@@ -0,0 +1,417 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+
+const HISTORY_SIZE: usize = 1000;
+
+
+#[derive(Debug, PartialEq)]
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    KillToEnd,
+    KillToStart,
+    Interrupt,
+    Eof,
+    Unknown,
+}
+
+
+#[derive(Debug, PartialEq)]
+struct LineBuffer {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+
+pub struct LineEditor {
+    history: Vec<String>,
+    history_path: Option<PathBuf>,
+    // Lines in the history file, which is appended to and rewritten once it holds twice HISTORY_SIZE
+    file_lines: usize,
+    // Settings saved by stty -g, None when stdin is not a terminal
+    terminal: Option<String>,
+}
+
+
+fn read_byte(reader: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0_u8; 1];
+    match reader.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+
+fn read_escape(reader: &mut impl Read) -> io::Result<Key> {
+    // ESC [ X, ESC O X and ESC [ n ~
+    let key = match read_byte(reader)? {
+        Some(b'[') | Some(b'O') => match read_byte(reader)? {
+            Some(b'A') => Key::Up,
+            Some(b'B') => Key::Down,
+            Some(b'C') => Key::Right,
+            Some(b'D') => Key::Left,
+            Some(b'H') => Key::Home,
+            Some(b'F') => Key::End,
+            Some(n @ b'0'..=b'9') => {
+                let mut code = vec![n];
+                loop {
+                    match read_byte(reader)? {
+                        Some(b'~') | None => break,
+                        Some(b) => code.push(b),
+                    }
+                }
+                match code.as_slice() {
+                    b"1" | b"7" => Key::Home,
+                    b"3" => Key::Delete,
+                    b"4" | b"8" => Key::End,
+                    _ => Key::Unknown,
+                }
+            },
+            _ => Key::Unknown,
+        },
+        _ => Key::Unknown,
+    };
+    Ok(key)
+}
+
+
+fn read_key(reader: &mut impl Read) -> io::Result<Key> {
+    let first = match read_byte(reader)? {
+        Some(b) => b,
+        None => return Ok(Key::Eof),
+    };
+    let key = match first {
+        b'\r' | b'\n' => Key::Enter,
+        1 => Key::Home,
+        2 => Key::Left,
+        3 => Key::Interrupt,
+        4 => Key::Eof,
+        5 => Key::End,
+        6 => Key::Right,
+        8 | 127 => Key::Backspace,
+        11 => Key::KillToEnd,
+        14 => Key::Down,
+        16 => Key::Up,
+        21 => Key::KillToStart,
+        27 => read_escape(reader)?,
+        b if b < 0x20 => Key::Unknown,
+        b => {
+            // The length of a UTF-8 sequence is given by its first byte
+            let len = match b {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            let mut bytes = vec![b];
+            while bytes.len() < len {
+                match read_byte(reader)? {
+                    Some(b) => bytes.push(b),
+                    None => break,
+                }
+            }
+            match std::str::from_utf8(&bytes).ok().and_then(|s| s.chars().next()) {
+                Some(c) => Key::Char(c),
+                None => Key::Unknown,
+            }
+        },
+    };
+    Ok(key)
+}
+
+
+impl LineBuffer {
+    fn new() -> LineBuffer {
+        LineBuffer {chars: Vec::new(), cursor: 0}
+    }
+
+    fn set(&mut self, s: &str) {
+        self.chars = s.chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    fn line(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    fn insert(&mut self, c: char) {
+        self.chars.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.chars.len() {
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.chars.len());
+    }
+
+    fn kill_to_end(&mut self) {
+        self.chars.truncate(self.cursor);
+    }
+
+    fn kill_to_start(&mut self) {
+        self.chars.drain(..self.cursor);
+        self.cursor = 0;
+    }
+}
+
+
+fn stty(args: &[&str]) -> Option<String> {
+    // stty works on the terminal given as its stdin
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).stderr(Stdio::null()).output().ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
+
+
+// Raw mode lasts as long as this value, so the terminal is restored on every return and on a panic
+struct RawMode {
+    settings: String,
+}
+
+
+impl RawMode {
+    fn enter(settings: &str) -> RawMode {
+        stty(&["-icanon", "-echo", "-isig", "min", "1"]);
+        RawMode {settings: settings.to_string()}
+    }
+}
+
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[self.settings.as_str()]);
+    }
+}
+
+
+fn history_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".computor_history"))
+}
+
+
+fn write_history(path: &Path, history: &[String]) {
+    // History is best effort, a read-only home must not stop the loop
+    let _ = fs::write(path, history.iter().map(|line| format!("{}\n", line)).collect::<String>());
+}
+
+
+fn load_history(path: &Path) -> Vec<String> {
+    // Only the last HISTORY_SIZE lines are kept, in the file as well
+    let mut history: Vec<String> = fs::read_to_string(path).unwrap_or_default().lines().map(|s| s.to_string()).collect();
+    if history.len() > HISTORY_SIZE {
+        history.drain(..history.len() - HISTORY_SIZE);
+        write_history(path, &history);
+    }
+    history
+}
+
+
+impl LineEditor {
+    pub fn new() -> LineEditor {
+        let history_path = history_path();
+        let history = match &history_path {
+            Some(path) => load_history(path),
+            None => Vec::new(),
+        };
+        LineEditor {file_lines: history.len(), history, history_path, terminal: stty(&["-g"])}
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.terminal.is_some()
+    }
+
+    pub fn add_history(&mut self, line: &str) {
+        if line.trim().is_empty() || self.history.last().map(|s| s.as_str()) == Some(line) {
+            return
+        }
+        self.history.push(line.to_string());
+        if self.history.len() > HISTORY_SIZE {
+            self.history.remove(0);
+        }
+        if let Some(path) = &self.history_path {
+            if self.file_lines >= 2 * HISTORY_SIZE {
+                write_history(path, &self.history);
+                self.file_lines = self.history.len();
+            } else if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+                // Appending is best effort too
+                let _ = writeln!(file, "{}", line);
+                self.file_lines += 1;
+            }
+        }
+    }
+
+    pub fn read_line(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        let settings = match &self.terminal {
+            Some(settings) => settings.clone(),
+            None => {
+                let mut line = String::new();
+                if io::stdin().lock().read_line(&mut line)? == 0 {
+                    return Ok(None)
+                }
+                return Ok(Some(line.trim_end_matches(&['\n', '\r'][..]).to_string()))
+            },
+        };
+        let _raw_mode = RawMode::enter(&settings);
+        self.edit(prompt)
+    }
+
+    fn edit(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout();
+        let mut buffer = LineBuffer::new();
+        // Index into the history while browsing it, the line being typed is kept aside
+        let mut index = self.history.len();
+        let mut typed = String::new();
+        loop {
+            let tail = buffer.chars.len() - buffer.cursor;
+            write!(stdout, "\r{}{}\x1b[K", prompt, buffer.line())?;
+            if tail > 0 {
+                write!(stdout, "\x1b[{}D", tail)?;
+            }
+            stdout.flush()?;
+            match read_key(&mut stdin)? {
+                Key::Char(c) => buffer.insert(c),
+                Key::Enter => break,
+                Key::Backspace => buffer.backspace(),
+                Key::Delete => buffer.delete(),
+                Key::Left => buffer.left(),
+                Key::Right => buffer.right(),
+                Key::Home => buffer.cursor = 0,
+                Key::End => buffer.cursor = buffer.chars.len(),
+                Key::KillToEnd => buffer.kill_to_end(),
+                Key::KillToStart => buffer.kill_to_start(),
+                Key::Up => {
+                    if index > 0 {
+                        if index == self.history.len() {
+                            typed = buffer.line();
+                        }
+                        index -= 1;
+                        buffer.set(&self.history[index]);
+                    }
+                },
+                Key::Down => {
+                    if index < self.history.len() {
+                        index += 1;
+                        buffer.set(self.history.get(index).unwrap_or(&typed));
+                    }
+                },
+                Key::Interrupt => {
+                    // Ctrl-C abandons the line
+                    writeln!(stdout, "^C")?;
+                    buffer = LineBuffer::new();
+                    index = self.history.len();
+                },
+                Key::Eof => {
+                    if buffer.chars.is_empty() {
+                        writeln!(stdout)?;
+                        return Ok(None)
+                    }
+                    buffer.delete();
+                },
+                Key::Unknown => (),
+            }
+        }
+        writeln!(stdout)?;
+        Ok(Some(buffer.line()))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(bytes: &[u8]) -> Vec<Key> {
+        let mut reader = bytes;
+        let mut vec = Vec::new();
+        loop {
+            match read_key(&mut reader).unwrap() {
+                Key::Eof => return vec,
+                key => vec.push(key),
+            }
+        }
+    }
+
+    #[test]
+    fn read_key_arrows() {
+        assert_eq!(keys(b"\x1b[A\x1b[B\x1b[C\x1b[D\x1bOH"), vec![Key::Up, Key::Down, Key::Right, Key::Left, Key::Home]);
+    }
+
+    #[test]
+    fn read_key_delete() {
+        assert_eq!(keys(b"\x1b[3~\x7f"), vec![Key::Delete, Key::Backspace]);
+    }
+
+    #[test]
+    fn read_key_multibyte() {
+        assert_eq!(keys("X√\r".as_bytes()), vec![Key::Char('X'), Key::Char('√'), Key::Enter]);
+    }
+
+    #[test]
+    fn line_buffer_edit() {
+        let mut buffer = LineBuffer::new();
+        for c in "X=2".chars() {
+            buffer.insert(c);
+        }
+        buffer.left();
+        buffer.left();
+        buffer.insert(' ');
+        buffer.backspace();
+        buffer.backspace();
+        assert_eq!(buffer, LineBuffer {chars: vec!['=', '2'], cursor: 0});
+    }
+
+    #[test]
+    fn load_history_truncated() {
+        let path = std::env::temp_dir().join(format!("computor_history_test_{}", std::process::id()));
+        let lines: String = (0..HISTORY_SIZE + 5).map(|i| format!("X = {}\n", i)).collect();
+        fs::write(&path, lines).unwrap();
+        let history = load_history(&path);
+        let file = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!((history.len(), history[0].as_str()), (HISTORY_SIZE, "X = 5"));
+        assert_eq!(file.lines().count(), HISTORY_SIZE);
+    }
+
+    #[test]
+    fn line_buffer_kill() {
+        let mut buffer = LineBuffer::new();
+        buffer.set("X^2 = 4");
+        buffer.cursor = 3;
+        buffer.kill_to_start();
+        buffer.right();
+        buffer.kill_to_end();
+        assert_eq!(buffer.line(), " ".to_string());
+    }
+}
@@ -1,66 +1,360 @@
+mod batch;
+mod line_editor;
+mod repl;
+
 use std::env;
+use std::io::{self, Write};
+use std::process::{self, ExitCode};
 
-mod term;
-mod parse_string;
-mod elem_to_term;
-mod reduce_equation;
-mod utility;
-mod solution;
-mod fraction;
-mod math_utility;
-
-use parse_string::parse_string;
-use elem_to_term::elem_to_term;
-use reduce_equation::reduce_equation;
-use utility::{
-    hash_terms_to_sorted_vec,
-    make_reduced_form_string,
-    evaluate_degree_of_terms,
-};
-use solution::solution;
-
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Please specify one argument");
-        return;
+use computor_v1::{reduce, solve, Polynomial, Solution, make_error_string, make_solution_json, make_error_json,
+    make_reduced_form_latex, make_solution_latex, make_steps_string, make_variable_solution_string, make_excluded_zero_string};
+
+
+const EXIT_IO_ERROR: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+const EXIT_PARSE_ERROR: u8 = 3;
+const EXIT_UNSOLVABLE: u8 = 4;
+const EXIT_NO_SOLUTION: u8 = 5;
+
+const USAGE: &str = "\
+Usage: computor [OPTIONS] [EQUATION]
+       computor [OPTIONS] --batch [FILE]
+
+Solves a polynomial equation in X, or in any other single letter, such as
+\"5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0\".
+Without an equation, equations are read interactively one per line.
+
+Options:
+  -b, --batch          Solve each line of FILE, or of the standard input without FILE
+  -f, --format FORMAT  Output format: text (default), json or latex
+  -n, --notation NOTATION
+                       Reduced form in text: standard (default), natural (X^2 + 2X + 1 = 0)
+                       or superscript (X² + 2X + 1 = 0)
+  -s, --steps          Explain each step of the solving, with the text format
+  -h, --help           Print this help
+  -V, --version        Print the version
+
+Exit status:
+  0  The equation was solved
+  1  The batch file could not be read
+  2  The command line was invalid
+  3  The equation could not be parsed, or a line of the batch could not be parsed
+  4  The coefficients are too large to solve the equation
+  5  The equation has no solution";
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Latex,
+    // Text with the explanation of each step
+    Steps,
+}
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Notation {
+    Standard,
+    Natural,
+    Superscript,
+}
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Output {
+    pub format: Format,
+    // Only the text formats use the notation
+    pub notation: Notation,
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Options<'a> {
+    output: Output,
+    batch: bool,
+    // The equation, or the file to read with --batch
+    argument: Option<&'a String>,
+}
+
+
+#[derive(Debug, PartialEq)]
+enum Command<'a> {
+    Help,
+    Version,
+    Run(Options<'a>),
+}
+
+
+fn parse_format(value: Option<&str>) -> Result<Format, String> {
+    match value {
+        Some("text") => Ok(Format::Text),
+        Some("json") => Ok(Format::Json),
+        Some("latex") => Ok(Format::Latex),
+        Some(other) => Err(format!("Unknown format: {}", other)),
+        None => Err("Please specify a format after --format".to_string()),
+    }
+}
+
+
+fn parse_notation(value: Option<&str>) -> Result<Notation, String> {
+    match value {
+        Some("standard") => Ok(Notation::Standard),
+        Some("natural") => Ok(Notation::Natural),
+        Some("superscript") => Ok(Notation::Superscript),
+        Some(other) => Err(format!("Unknown notation: {}", other)),
+        None => Err("Please specify a notation after --notation".to_string()),
+    }
+}
+
+
+impl Output {
+    pub fn reduced_form(&self, polynomial: &Polynomial) -> String {
+        match self.notation {
+            Notation::Standard => polynomial.reduced_form(),
+            Notation::Natural => polynomial.natural_form(false),
+            Notation::Superscript => polynomial.natural_form(true),
+        }
     }
+}
 
-    let equation_string = &args[1];
 
-    let parsed_equation = match parse_string(equation_string) {
+fn parse_args(args: &[String]) -> Result<Command<'_>, String> {
+    let mut format = Format::Text;
+    let mut notation = Notation::Standard;
+    let mut batch = false;
+    let mut steps = false;
+    let mut argument = None;
+    let mut is_option_end = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        // An equation may start with a minus, so only the known short options are taken as options
+        match arg.as_str() {
+            _ if is_option_end => (),
+            "--" => {
+                is_option_end = true;
+                continue
+            },
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-b" | "--batch" => {
+                batch = true;
+                continue
+            },
+            "-s" | "--steps" => {
+                steps = true;
+                continue
+            },
+            "-f" | "--format" => {
+                format = parse_format(iter.next().map(|s| s.as_str()))?;
+                continue
+            },
+            s if s.starts_with("--format=") => {
+                format = parse_format(Some(&s["--format=".len()..]))?;
+                continue
+            },
+            "-n" | "--notation" => {
+                notation = parse_notation(iter.next().map(|s| s.as_str()))?;
+                continue
+            },
+            s if s.starts_with("--notation=") => {
+                notation = parse_notation(Some(&s["--notation=".len()..]))?;
+                continue
+            },
+            s if s.starts_with("--") => return Err(format!("Unknown option: {}", s)),
+            _ => (),
+        }
+        if argument.is_some() {
+            return Err("Please specify one argument".to_string());
+        }
+        argument = Some(arg);
+    }
+    if steps {
+        if format != Format::Text {
+            return Err("--steps can only be used with the text format".to_string());
+        }
+        if batch {
+            return Err("--steps cannot be used with --batch".to_string());
+        }
+        format = Format::Steps;
+    }
+    Ok(Command::Run(Options {output: Output {format, notation}, batch, argument}))
+}
+
+
+fn write_solution(out: &mut impl Write, output: Output, equation_string: &str, polynomial: &Polynomial, solution: &Solution) -> io::Result<()> {
+    match output.format {
+        Format::Steps => writeln!(out, "{}", make_steps_string(equation_string, polynomial, solution))?,
+        Format::Json => writeln!(out, "{}", make_solution_json(polynomial, solution))?,
+        Format::Latex => {
+            writeln!(out, "{}", make_reduced_form_latex(polynomial.terms(), polynomial.variable()))?;
+            writeln!(out, "{}", make_solution_latex(polynomial, solution))?;
+        },
+        Format::Text => {
+            writeln!(out, "Reduced form: {}", output.reduced_form(polynomial))?;
+            writeln!(out, "Polynomial degree: {}", polynomial.degree())?;
+            writeln!(out, "{}", make_variable_solution_string(solution, polynomial.variable()))?;
+            if polynomial.excludes_zero() {
+                writeln!(out, "{}", make_excluded_zero_string(polynomial.variable()))?;
+            }
+        },
+    }
+    Ok(())
+}
+
+
+pub fn exit_on_write_error(result: io::Result<()>) {
+    // A reader that went away, as with | head, ends the program quietly instead of a panic
+    match result {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => process::exit(0),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(EXIT_IO_ERROR as i32)
+        },
+    }
+}
+
+
+pub fn solve_and_print(output: Output, equation_string: &str) -> Option<(Polynomial, Solution)> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let polynomial = match reduce(equation_string) {
         Ok(v) => v,
-        Err(s) => {
-            eprintln!("{}", s);
-            return;
+        Err(e) => {
+            if output.format == Format::Json {
+                exit_on_write_error(writeln!(out, "{}", make_error_json(&e)));
+            } else {
+                eprintln!("{}", make_error_string(equation_string, &e));
+            }
+            return None;
         }
     };
 
-    let equation_terms = match elem_to_term(parsed_equation) {
-        Ok(v) => v,
-        Err(s) => {
-            eprintln!("{}", s);
-            return;
+    let solution = solve(&polynomial);
+    exit_on_write_error(write_solution(&mut out, output, equation_string, &polynomial, &solution));
+    Some((polynomial, solution))
+}
+
+
+fn run(options: Options) -> u8 {
+    if options.batch {
+        return match batch::run(options.output, options.argument) {
+            Ok(0) => 0,
+            Ok(_) => EXIT_PARSE_ERROR,
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                EXIT_IO_ERROR
+            },
         }
+    }
+
+    // Without an equation, equations are read one per line
+    let equation_string = match options.argument {
+        Some(v) => v,
+        None => {
+            repl::run(options.output);
+            return 0
+        },
+    };
+
+    match solve_and_print(options.output, equation_string) {
+        None => EXIT_PARSE_ERROR,
+        Some((_, Solution::Unsolvable)) => EXIT_UNSOLVABLE,
+        Some((_, Solution::NoSolution)) => EXIT_NO_SOLUTION,
+        Some(_) => 0,
+    }
+}
+
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let code = match parse_args(&args[1..]) {
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            0
+        },
+        Ok(Command::Version) => {
+            println!("computor {}", env!("CARGO_PKG_VERSION"));
+            0
+        },
+        Ok(Command::Run(options)) => run(options),
+        Err(e) => {
+            eprintln!("{}\nTry 'computor --help' for more information.", e);
+            EXIT_USAGE
+        },
     };
+    ExitCode::from(code)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_equation() {
+        let args = args(&["--format=json", "-3 + X = 0"]);
+        assert_eq!(parse_args(&args), Ok(Command::Run(Options {output: Output {format: Format::Json, notation: Notation::Standard}, batch: false, argument: Some(&args[1])})));
+    }
 
-    let terms = reduce_equation(&equation_terms.0, &equation_terms.1);
+    #[test]
+    fn parse_args_batch_stdin() {
+        let args = args(&["-b", "-f", "latex"]);
+        assert_eq!(parse_args(&args), Ok(Command::Run(Options {output: Output {format: Format::Latex, notation: Notation::Standard}, batch: true, argument: None})));
+    }
 
-    let terms = hash_terms_to_sorted_vec(terms);
+    #[test]
+    fn parse_args_option_end() {
+        let args = args(&["--", "--help"]);
+        assert_eq!(parse_args(&args), Ok(Command::Run(Options {output: Output {format: Format::Text, notation: Notation::Standard}, batch: false, argument: Some(&args[1])})));
+    }
+
+    #[test]
+    fn parse_args_help() {
+        assert_eq!(parse_args(&args(&["X = 1", "--help"])), Ok(Command::Help));
+    }
 
-    println!("Reduced form: {}", make_reduced_form_string(&terms));
+    #[test]
+    fn parse_args_unknown_option() {
+        assert_eq!(parse_args(&args(&["--verbose"])), Err("Unknown option: --verbose".to_string()));
+    }
 
-    let degree = evaluate_degree_of_terms(&terms);
+    #[test]
+    fn parse_args_two_equations() {
+        assert_eq!(parse_args(&args(&["X = 1", "X = 2"])), Err("Please specify one argument".to_string()));
+    }
 
-    println!("Polynomial degree: {}", degree);
+    #[test]
+    fn parse_args_steps() {
+        let args = args(&["--steps", "X = 1"]);
+        assert_eq!(parse_args(&args), Ok(Command::Run(Options {output: Output {format: Format::Steps, notation: Notation::Standard}, batch: false, argument: Some(&args[1])})));
+    }
 
-    if degree >= 3 {
-        println!("The polynomial degree is strictly greater than 2, I can't solve.");
-        return;
+    #[test]
+    fn parse_args_steps_json() {
+        assert_eq!(parse_args(&args(&["-s", "-f", "json"])), Err("--steps can only be used with the text format".to_string()));
     }
 
-    let solved_string = solution(&terms, degree);
+    #[test]
+    fn write_solution_text() {
+        let polynomial = reduce("X^2 = 4").unwrap();
+        let mut out = Vec::new();
+        let output = Output {format: Format::Text, notation: Notation::Natural};
+        write_solution(&mut out, output, "X^2 = 4", &polynomial, &solve(&polynomial)).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(),
+            "Reduced form: X^2 - 4 = 0\nPolynomial degree: 2\nDiscriminant is strictly positive, the two solutions are:\n2\n-2\n");
+    }
 
-    println!("{}", solved_string);
+    #[test]
+    fn parse_args_notation() {
+        let args = args(&["-n", "superscript", "X = 1"]);
+        let output = Output {format: Format::Text, notation: Notation::Superscript};
+        assert_eq!(parse_args(&args), Ok(Command::Run(Options {output, batch: false, argument: Some(&args[2])})));
+    }
 }
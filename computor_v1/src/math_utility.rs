@@ -1,4 +1,7 @@
+#[allow(clippy::module_inception)]
 pub mod math_utility {
+    pub use std::f64::consts::{PI, E};
+
     pub fn checked_abs(a: i64) -> Option<i64> {
         if a < 0 {
             a.checked_neg()
@@ -17,14 +20,8 @@ pub mod math_utility {
     }
 
     pub fn checked_gcd(a: i64, b: i64) -> Option<i64> {
-        let a = match checked_abs(a) {
-            Some(v) => v,
-            None => return None
-        };
-        let b = match checked_abs(b) {
-            Some(v) => v,
-            None => return None
-        };
+        let a = checked_abs(a)?;
+        let b = checked_abs(b)?;
         if a == 0 {
             return Some(b);
         }
@@ -38,12 +35,220 @@ pub mod math_utility {
         if v <= 0.0 {
             return 0.0
         }
-        let mut x = v;
+        if !v.is_finite() {
+            return v
+        }
+        // v = m * 4^k with m in [1, 4), so that Newton's method stays in range and sqrt(v) = sqrt(m) * 2^k
+        let mut m = v;
+        let mut scale = 1.0;
+        while m >= 4.0 {
+            m /= 4.0;
+            scale *= 2.0;
+        }
+        while m < 1.0 {
+            m *= 4.0;
+            scale /= 2.0;
+        }
+        let mut x = m;
+        for _ in 0..100 {
+            let next = (x + m / x) / 2.0;
+            if next == x {
+                break;
+            }
+            x = next;
+        }
+        // m and the root are whole multiples of 2^-52, so the nearest root is settled exactly in integers
+        let unit = 4503599627370496.0;
+        let n = (m * unit) as u128 * unit as u128;
+        let mut root = (x * unit) as u128;
+        while root * root > n {
+            root -= 1;
+        }
+        while (root + 1) * (root + 1) <= n {
+            root += 1;
+        }
+        if n - root * root > root {
+            root += 1;
+        }
+        root as f64 / unit * scale
+    }
+
+    pub fn cbrt(v: f64) -> f64 {
+        if v == 0.0 {
+            return 0.0
+        }
+        if v < 0.0 {
+            return -cbrt(-v)
+        }
+        let mut x = if v > 1.0 {sqrt(v)} else {1.0};
         for _ in 0..1000 {
-            x = (x * x + v) / (2.0 * x);
+            let next = (2.0 * x + v / (x * x)) / 3.0;
+            if next == x {
+                break;
+            }
+            x = next;
         }
         x
     }
+
+    fn power(x: f64, n: u32) -> f64 {
+        let mut result = 1.0;
+        for _ in 0..n {
+            result *= x;
+        }
+        result
+    }
+
+    pub fn nth_root(v: f64, n: u32) -> f64 {
+        if v <= 0.0 || n == 0 {
+            return 0.0
+        }
+        if !v.is_finite() {
+            return v
+        }
+        // v = m * 2^(nk) with m in [1, 2^n), so that the root of m is found between 1 and 2
+        let mut m = v;
+        let mut exponent: i64 = 0;
+        while m >= 2.0 {
+            m /= 2.0;
+            exponent += 1;
+        }
+        while m < 1.0 {
+            m *= 2.0;
+            exponent -= 1;
+        }
+        let k = exponent.div_euclid(n as i64);
+        m *= power(2.0, exponent.rem_euclid(n as i64) as u32);
+        let mut low = 1.0;
+        let mut high = 2.0;
+        for _ in 0..64 {
+            let middle = (low + high) / 2.0;
+            if power(middle, n) > m {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        let mut root = low;
+        for _ in 0..k.abs() {
+            root = if k > 0 {root * 2.0} else {root / 2.0};
+        }
+        root
+    }
+
+    pub fn cos(v: f64) -> f64 {
+        let two_pi = 2.0 * PI;
+        let mut x = v - two_pi * (v / two_pi).round();
+        if x > PI {
+            x -= two_pi;
+        } else if x < -PI {
+            x += two_pi;
+        }
+        let mut term = 1.0;
+        let mut sum = 1.0;
+        for i in 1..40 {
+            let n = (2 * i) as f64;
+            term *= -x * x / ((n - 1.0) * n);
+            sum += term;
+        }
+        sum
+    }
+
+    pub fn acos(v: f64) -> f64 {
+        if v >= 1.0 {
+            return 0.0
+        }
+        if v <= -1.0 {
+            return PI
+        }
+        let mut low = 0.0;
+        let mut high = PI;
+        for _ in 0..200 {
+            let middle = (low + high) / 2.0;
+            if cos(middle) > v {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    pub fn divisors(a: i64) -> Option<Vec<i64>> {
+        let a = checked_abs(a)?;
+        if a == 0 || a > 1_000_000_000_000 {
+            return None
+        }
+        let mut small = Vec::new();
+        let mut large = Vec::new();
+        let mut i = 1;
+        while i * i <= a {
+            if a % i == 0 {
+                small.push(i);
+                if i * i != a {
+                    large.push(a / i);
+                }
+            }
+            i += 1;
+        }
+        large.reverse();
+        small.extend(large);
+        Some(small)
+    }
+
+    pub fn int_sqrt(a: i64) -> Option<i64> {
+        // Floor of the square root, the float estimate is corrected by a few steps
+        if a < 0 {
+            return None
+        }
+        let mut root = sqrt(a as f64) as i64;
+        loop {
+            match root.checked_mul(root) {
+                Some(n) if n <= a => break,
+                _ => root -= 1,
+            }
+        }
+        while let Some(n) = (root + 1).checked_mul(root + 1) {
+            if n > a {
+                break
+            }
+            root += 1;
+        }
+        Some(root)
+    }
+
+    pub fn square_factor(a: i64) -> Option<(i64, i64)> {
+        // a = k^2 * m with m square free
+        if a <= 0 {
+            return None
+        }
+        let mut rest = a;
+        let mut k = 1;
+        let mut m = 1;
+        let mut p = 2;
+        while p <= rest / p / p {
+            let mut count = 0;
+            while rest % p == 0 {
+                rest /= p;
+                count += 1;
+            }
+            for _ in 0..count / 2 {
+                k *= p;
+            }
+            if count % 2 == 1 {
+                m *= p;
+            }
+            p += 1;
+        }
+        // Every prime factor left is above the cube root, so the rest is 1, q, q * r or q^2
+        let root = int_sqrt(rest)?;
+        if root * root == rest {
+            k *= root;
+        } else {
+            m *= rest;
+        }
+        Some((k, m))
+    }
 }
 
 #[cfg(test)]
@@ -132,6 +337,127 @@ mod tests {
 
     #[test]
     fn sqrt_max() {
-        assert!(sqrt(f64::MAX).is_nan());
+        assert_eq!(sqrt(f64::MAX), f64::MAX.sqrt());
+    }
+
+    #[test]
+    fn sqrt_any_scale() {
+        for v in [2.0, 1e160, 2e200, 1e-100, 5e-324, 0.3] {
+            assert_eq!(sqrt(v), f64::sqrt(v));
+        }
+    }
+
+    #[test]
+    fn nth_root_exact() {
+        assert_eq!(nth_root(32.0, 5), 2.0);
+        assert_eq!(nth_root(0.0, 5), 0.0);
+    }
+
+    #[test]
+    fn nth_root_any_scale() {
+        assert!((nth_root(1e-100, 5) / 1e-20 - 1.0).abs() < 1e-15);
+        assert!((nth_root(1e300, 5) / 1e60 - 1.0).abs() < 1e-15);
+        assert!((nth_root(1e300, 1000) / 1.9952623149688795 - 1.0).abs() < 1e-15);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn cbrt_zero() {
+        assert_eq!(cbrt(0.0), 0.0);
+    }
+
+    #[test]
+    fn cbrt_8() {
+        assert_eq!(cbrt(8.0), 2.0);
+    }
+
+    #[test]
+    fn cbrt_minus_27() {
+        assert_eq!(cbrt(-27.0), -3.0);
+    }
+
+    #[test]
+    fn cbrt_small() {
+        assert!((cbrt(0.001) - 0.1).abs() < 1e-15);
+    }
+
+    #[test]
+    fn cos_zero() {
+        assert_eq!(cos(0.0), 1.0);
+    }
+
+    #[test]
+    fn cos_pi() {
+        assert!((cos(PI) + 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn cos_large() {
+        assert!((cos(100.0 * PI + PI / 3.0) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn acos_one() {
+        assert!(acos(1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn acos_minus_one() {
+        assert!((acos(-1.0) - PI).abs() < 1e-15);
+    }
+
+    #[test]
+    fn acos_half() {
+        assert!((acos(0.5) - PI / 3.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn divisors_zero() {
+        assert_eq!(divisors(0), None);
+    }
+
+    #[test]
+    fn int_sqrt_floor() {
+        assert_eq!(int_sqrt(99), Some(9));
+    }
+
+    #[test]
+    fn int_sqrt_maximum() {
+        assert_eq!(int_sqrt(i64::MAX), Some(3037000499));
+    }
+
+    #[test]
+    fn square_factor_45() {
+        assert_eq!(square_factor(45), Some((3, 5)));
+    }
+
+    #[test]
+    fn square_factor_square() {
+        assert_eq!(square_factor(144), Some((12, 1)));
+    }
+
+    #[test]
+    fn square_factor_large_prime_square() {
+        // 2 * 1000003^2
+        assert_eq!(square_factor(2000012000018), Some((1000003, 2)));
+    }
+
+    #[test]
+    fn square_factor_zero() {
+        assert_eq!(square_factor(0), None);
+    }
+
+    #[test]
+    fn divisors_12() {
+        assert_eq!(divisors(12), Some(vec![1, 2, 3, 4, 6, 12]));
+    }
+
+    #[test]
+    fn divisors_minus_9() {
+        assert_eq!(divisors(-9), Some(vec![1, 3, 9]));
+    }
+
+    #[test]
+    fn divisors_too_large() {
+        assert_eq!(divisors(i64::MAX), None);
+    }
+}
@@ -1,148 +1,539 @@
-use crate::term::Elem;
+use crate::term::{Elem, Token, Constant, Function};
+use crate::error::{ErrorKind, ParseError, Span};
+use crate::big_int::BigInt;
+use crate::big_fraction::BigFraction;
 
 
-fn str_to_float(num_str: &String) -> Result<f64, String> {
+fn str_to_float(num_str: &str) -> Result<f64, ErrorKind> {
     match num_str.parse() {
         Ok(n) => Ok(n),
         Err(_) => {
-            Err("Conversion Failure".to_string())
+            Err(ErrorKind::MalformedNumber)
         }
     }
 }
 
 
-fn str_to_int(num_str: &String) -> Result<i64, String> {
+fn power_of_ten(n: usize) -> BigInt {
+    BigInt::parse(&format!("1{}", "0".repeat(n))).unwrap()
+}
+
+
+fn str_to_fraction(num_str: &str) -> Option<BigFraction> {
+    // "9.3" is read exactly as 93 / 10, however many digits there are
+    let (int_part, decimal_part) = num_str.split_once('.')?;
+    let top = BigInt::parse(&format!("{}{}", int_part, decimal_part))?;
+    BigFraction::new(top, power_of_ten(decimal_part.len()))
+}
+
+
+fn str_to_int(num_str: &str) -> Result<i64, ErrorKind> {
     match num_str.parse() {
         Ok(n) => Ok(n),
         Err(_) => {
-            Err("Conversion Failure".to_string())
+            Err(ErrorKind::MalformedNumber)
         }
     }
 }
 
 
-fn update_vec_str_to_num(is_float: &mut bool, num_str: &mut String, vec: &mut Vec<Elem>) -> Result<(), String> {
-    if *is_float {
-        *is_float = false;
-        match str_to_float(&num_str) {
-            Ok(n) => vec.push(Elem::NumFloat(n)),
-            Err(s) => return Err(s),
-        };
+fn int_elem(digits: &str, span: Span) -> Result<Elem, ParseError> {
+    match str_to_int(digits) {
+        Ok(n) => Ok(Elem::NumInt(n)),
+        Err(kind) => match BigInt::parse(digits) {
+            Some(n) => Ok(Elem::NumBig(n)),
+            None => Err(ParseError::new(kind, span)),
+        },
+    }
+}
+
+
+fn float_elem(num_str: &str, span: Span) -> Result<Elem, ParseError> {
+    // A number that overflows, or loses its digits to underflow, is refused rather than read as inf or 0
+    let is_zero = num_str.bytes().take_while(|b| !matches!(b, b'e' | b'E')).all(|b| matches!(b, b'0' | b'.'));
+    match str_to_float(num_str) {
+        Ok(n) if n.is_normal() || n == 0.0 && is_zero => Ok(Elem::NumFloat(n)),
+        Ok(_) => Err(ParseError::new(ErrorKind::NumberOutOfRange, span)),
+        Err(kind) => Err(ParseError::new(kind, span)),
+    }
+}
+
+
+fn fraction_elem(fraction: BigFraction) -> Elem {
+    match fraction.to_fraction() {
+        Some(f) => Elem::NumFraction(f),
+        None => Elem::NumBigFraction(fraction),
+    }
+}
+
+
+fn decimal_elem(num_str: &str, span: Span) -> Result<Elem, ParseError> {
+    match str_to_fraction(num_str) {
+        Some(f) => Ok(fraction_elem(f)),
+        None => float_elem(num_str, span),
+    }
+}
+
+
+fn exponent_elem(digits: &str, scale: i64, num_str: &str, span: Span) -> Result<Elem, ParseError> {
+    // digits * 10^scale, kept exact unless it is too large or too small
+    if (0..=1000).contains(&scale) {
+        return int_elem(&format!("{}{}", digits, "0".repeat(scale as usize)), span)
+    }
+    // Out of the range of f64 it is refused like a float, a small one is still kept exact
+    let elem = float_elem(num_str, span)?;
+    let exact = if scale < 0 {
+        BigInt::parse(digits).and_then(|top| BigFraction::new(top, power_of_ten(scale.unsigned_abs() as usize)))
     } else {
-        match str_to_int(&num_str) {
-            Ok(n) => vec.push(Elem::NumInt(n)),
-            Err(s) => return Err(s),
-        };
+        None
+    };
+    Ok(exact.map_or(elem, fraction_elem))
+}
+
+
+fn read_radix_number(equation: &str, start: usize, radix: u32) -> Result<(Elem, usize), ParseError> {
+    // 0x1F or 0b101, with _ allowed between digits
+    let bytes = equation.as_bytes();
+    let mut value = BigInt::from_i64(0);
+    let mut index = start + 2;
+    while let Some(&b) = bytes.get(index) {
+        let c = b as char;
+        let is_separator = c == '_' && matches!(bytes.get(index + 1), Some(b) if (*b as char).is_digit(radix));
+        if let Some(digit) = c.to_digit(radix) {
+            value = value.mul(&BigInt::from_i64(radix as i64)).add(&BigInt::from_i64(digit as i64));
+        } else if !is_separator && (c.is_ascii_digit() || c == '_' || c == '.') {
+            return Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(start, index + 1)))
+        } else if !is_separator {
+            break
+        }
+        index += 1;
     }
-    num_str.clear();
-    Ok(())
+    let elem = match value.to_i64() {
+        Some(n) => Elem::NumInt(n),
+        None => Elem::NumBig(value),
+    };
+    Ok((elem, index))
+}
+
+
+fn read_number(equation: &str, start: usize) -> Result<(Elem, usize), ParseError> {
+    // Returns the number starting at start and the index right after it
+    let bytes = equation.as_bytes();
+    let is_digit_at = |index: usize| matches!(bytes.get(index), Some(b) if b.is_ascii_digit());
+    let radix = match (bytes[start], bytes.get(start + 1)) {
+        (b'0', Some(b'x' | b'X')) => 16,
+        (b'0', Some(b'b' | b'B')) => 2,
+        _ => 10,
+    };
+    // Without a digit after it, 0x is zero times x
+    if radix != 10 && matches!(bytes.get(start + 2), Some(b) if (*b as char).is_digit(radix)) {
+        return read_radix_number(equation, start, radix)
+    }
+
+    let mut digits = String::new();
+    let mut decimals = 0;
+    let mut is_float = false;
+    let mut index = start;
+    loop {
+        match bytes.get(index) {
+            Some(b) if b.is_ascii_digit() => {
+                digits.push(*b as char);
+                if is_float {
+                    decimals += 1;
+                }
+            },
+            Some(b'_') if index > start && is_digit_at(index - 1) && is_digit_at(index + 1) => {},
+            Some(b'_') => return Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(start, index + 1))),
+            Some(b'.') if is_float => return Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(start, index + 1))),
+            Some(b'.') => is_float = true,
+            _ => break,
+        }
+        index += 1;
+    }
+    if digits.is_empty() {
+        return Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(start, index)))
+    }
+
+    // 1e-3 or 2.5E4, where an e without digits after it is read as the letter e, the constant or the variable
+    let exponent_start = match (bytes.get(index), bytes.get(index + 1)) {
+        (Some(b'e' | b'E'), Some(b'+' | b'-')) if is_digit_at(index + 2) => Some(index + 1),
+        (Some(b'e' | b'E'), _) if is_digit_at(index + 1) => Some(index + 1),
+        _ => None,
+    };
+    let exponent = match exponent_start {
+        Some(exponent_start) => {
+            index = exponent_start + 1;
+            while is_digit_at(index) {
+                index += 1;
+            }
+            match equation[exponent_start..index].parse::<i64>() {
+                Ok(n) => Some(n),
+                Err(_) => return Err(ParseError::new(ErrorKind::NumberOutOfRange, Span::new(start, index))),
+            }
+        },
+        None => None,
+    };
+
+    let span = Span::new(start, index);
+    let num_str = equation[start..index].replace('_', "");
+    let elem = match exponent {
+        Some(exponent) => match exponent.checked_sub(decimals) {
+            Some(scale) => exponent_elem(&digits, scale, &num_str, span)?,
+            None => return Err(ParseError::new(ErrorKind::NumberOutOfRange, span)),
+        },
+        None if is_float => decimal_elem(&num_str, span)?,
+        None => int_elem(&num_str, span)?,
+    };
+    Ok((elem, index))
 }
 
 
-fn update_vec_char_to_elem_except_num(c: char, vec: &mut Vec<Elem>) -> Result<(), String> {
-    match c {
-        'X' => vec.push(Elem::X),
-        '+' => vec.push(Elem::Plus),
-        '-' => vec.push(Elem::Minus),
-        '*' => vec.push(Elem::Prod),
-        '^' => vec.push(Elem::Power),
-        '=' => vec.push(Elem::Equal),
-        ' ' => {},
+fn read_name(equation: &str, start: usize) -> Result<(Option<Elem>, usize), ParseError> {
+    // The word starting at start, with the constant or function it names if any
+    let end = equation[start..].find(|c: char| !c.is_alphabetic()).map_or(equation.len(), |n| start + n);
+    let word = &equation[start..end];
+    let elem = match (Constant::from_name(word), Function::from_name(word)) {
+        (Some(constant), _) => Some(Elem::Constant(constant)),
+        (_, Some(function)) => Some(Elem::Function(function)),
+        _ => None,
+    };
+    // xx(X + 1) is a product, but a word of different letters before a parenthesis is meant as a function
+    let mut letters = word.chars();
+    let first = letters.next();
+    let is_one_letter = letters.all(|c| matches!(first, Some(first) if is_same_variable(first, c)));
+    if elem.is_none() && !is_one_letter && equation[end..].trim_start().starts_with('(') {
+        return Err(ParseError::new(ErrorKind::UnknownFunction(word.to_string()), Span::new(start, end)))
+    }
+    Ok((elem, end))
+}
+
+
+fn update_vec_char_to_elem_except_num(c: char, index: usize, vec: &mut Vec<Token>) -> Result<(), ParseError> {
+    let elem = match c {
+        '+' => Elem::Plus,
+        '-' => Elem::Minus,
+        '*' => Elem::Prod,
+        '/' => Elem::Div,
+        '^' => Elem::Power,
+        '=' => Elem::Equal,
+        '(' => Elem::LeftParen,
+        ')' => Elem::RightParen,
+        ' ' => return Ok(()),
         _ => {
-            return Err(format!("Unsupported characters: {}", c));
+            let span = Span::new(index, index + c.len_utf8());
+            return Err(ParseError::new(ErrorKind::UnsupportedChar(c), span));
         }
     };
+    vec.push(Token::new(elem, index, index + 1));
     Ok(())
 }
 
 
-pub fn parse_string(equation: &String) -> Result<Vec<Elem>, String> {
+fn is_same_variable(a: char, b: char) -> bool {
+    a == b || a.eq_ignore_ascii_case(&b)
+}
+
+
+pub fn find_variable(equation: &str, tokens: &[Token]) -> char {
+    // The variable is named as it is first written, X when there is none
+    tokens.iter()
+        .find(|token| token.elem == Elem::X)
+        .and_then(|token| equation[token.span.start..].chars().next())
+        .unwrap_or('X')
+}
+
+
+pub fn parse_string(equation: &str) -> Result<Vec<Token>, ParseError> {
     let mut vec = Vec::new();
-    let mut num_str = String::new();
-    let mut is_float = false;
+    let mut variable = None;
+    // The characters of a number or a name already read are skipped
+    let mut read_end = 0;
+    // The letters of a word that names nothing are each the variable
+    let mut word_end = 0;
 
-    for c in equation.chars() {
+    for (index, c) in equation.char_indices() {
+        if index < read_end {
+            continue
+        }
         match c {
-            '0'..='9' => num_str.push(c),
-            '.' => {
-                if is_float {
-                    return Err("With multiple decimal places".to_string());
-                }
-                is_float = true;
-                num_str.push(c);
+            '0'..='9' | '.' => {
+                let (elem, end) = read_number(equation, index)?;
+                vec.push(Token::new(elem, index, end));
+                read_end = end;
             },
-            _ => {
-                if num_str.len() > 0 {
-                    update_vec_str_to_num(&mut is_float, &mut num_str, &mut vec)?;
+            c if c.is_alphabetic() => {
+                if index >= word_end {
+                    let (elem, end) = read_name(equation, index)?;
+                    if let Some(elem) = elem {
+                        vec.push(Token::new(elem, index, end));
+                        read_end = end;
+                        continue
+                    }
+                    word_end = end;
                 }
-                update_vec_char_to_elem_except_num(c, &mut vec)?;
-            }
+                let span = Span::new(index, index + c.len_utf8());
+                match variable {
+                    Some(v) if !is_same_variable(v, c) => {
+                        return Err(ParseError::new(ErrorKind::MixedVariables(v, c), span))
+                    },
+                    _ => variable = Some(c),
+                }
+                vec.push(Token {elem: Elem::X, span});
+            },
+            _ => update_vec_char_to_elem_except_num(c, index, &mut vec)?,
         }
     }
-    if num_str.len() > 0 {
-        update_vec_str_to_num(&mut is_float, &mut num_str, &mut vec)?;
+    // e is the constant, unless no other letter is the variable, as in e^2 = 4
+    let constant_e = vec.iter().position(|token| token.elem == Elem::Constant(Constant::E));
+    match (variable, constant_e) {
+        (None, Some(_)) => {
+            for token in vec.iter_mut().filter(|token| token.elem == Elem::Constant(Constant::E)) {
+                token.elem = Elem::X;
+            }
+        },
+        (Some(v), Some(i)) if is_same_variable(v, 'e') => return Err(ParseError::new(ErrorKind::VariableClash(v), vec[i].span)),
+        _ => {},
     }
-    return Ok(vec);
+    Ok(vec)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fraction::Fraction;
+
+    fn parse_elems(equation: &str) -> Result<Vec<Elem>, ParseError> {
+        parse_string(equation).map(|tokens| tokens.into_iter().map(|token| token.elem).collect())
+    }
 
     #[test]
     fn str_to_float_normal() {
-        assert_eq!(str_to_float(&"123.456".to_string()), Ok(123.456));
+        assert_eq!(str_to_float("123.456"), Ok(123.456));
     }
 
     #[test]
     fn str_to_float_not_number() {
-        assert_eq!(str_to_float(&"a".to_string()), Err("Conversion Failure".to_string()));
+        assert_eq!(str_to_float("a"), Err(ErrorKind::MalformedNumber));
+    }
+
+    #[test]
+    fn str_to_fraction_normal() {
+        assert_eq!(str_to_fraction("9.3").and_then(|f| f.to_fraction()), Fraction::safe_new(93, 10));
+    }
+
+    #[test]
+    fn str_to_fraction_irreducible() {
+        assert_eq!(str_to_fraction("0.50").and_then(|f| f.to_fraction()), Fraction::safe_new(1, 2));
+    }
+
+    #[test]
+    fn str_to_fraction_leading_dot() {
+        assert_eq!(str_to_fraction(".25").and_then(|f| f.to_fraction()), Fraction::safe_new(1, 4));
+    }
+
+    #[test]
+    fn str_to_fraction_long() {
+        let fraction = str_to_fraction("0.12345678901234567890123").unwrap();
+        assert_eq!(fraction.to_fraction(), None);
+        assert_eq!(fraction.to_decimal_string(), Some("0.12345678901234567890123".to_string()));
     }
 
     #[test]
     fn str_to_int_normal() {
-        assert_eq!(str_to_int(&"123".to_string()), Ok(123));
+        assert_eq!(str_to_int("123"), Ok(123));
     }
 
     #[test]
     fn str_to_int_float() {
-        assert_eq!(str_to_int(&"123.456".to_string()), Err("Conversion Failure".to_string()));
+        assert_eq!(str_to_int("123.456"), Err(ErrorKind::MalformedNumber));
     }
 
     #[test]
     fn str_to_int_not_number() {
-        assert_eq!(str_to_int(&"a".to_string()), Err("Conversion Failure".to_string()));
+        assert_eq!(str_to_int("a"), Err(ErrorKind::MalformedNumber));
     }
 
     #[test]
     fn str_to_int_maximum_plus() {
-        assert_eq!(str_to_int(&"9223372036854775808".to_string()), Err("Conversion Failure".to_string()));
+        assert_eq!(str_to_int("9223372036854775808"), Err(ErrorKind::MalformedNumber));
     }
 
     #[test]
     fn str_to_int_minimum_minus() {
-        assert_eq!(str_to_int(&"-9223372036854775809".to_string()), Err("Conversion Failure".to_string()));
+        assert_eq!(str_to_int("-9223372036854775809"), Err(ErrorKind::MalformedNumber));
     }
 
     #[test]
     fn parse_string_normal() {
         use Elem::*;
-        assert_eq!(parse_string(&"5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0".to_string()), Ok(vec![
-            NumInt(5), Prod, X, Power, NumInt(0), Plus, NumInt(4), Prod, X, Power, NumInt(1), Minus, NumFloat(9.3), Prod, X, Power, NumInt(2), Equal, NumInt(1), Prod, X, Power, NumInt(0)]));
+        assert_eq!(parse_elems("5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0"), Ok(vec![
+            NumInt(5), Prod, X, Power, NumInt(0), Plus, NumInt(4), Prod, X, Power, NumInt(1), Minus, NumFraction(Fraction::safe_new(93, 10).unwrap()), Prod, X, Power, NumInt(2), Equal, NumInt(1), Prod, X, Power, NumInt(0)]));
+    }
+
+    #[test]
+    fn parse_string_span() {
+        assert_eq!(parse_string("12.5 X"), Ok(vec![
+            Token::new(Elem::NumFraction(Fraction::safe_new(25, 2).unwrap()), 0, 4), Token::new(Elem::X, 5, 6)]));
+    }
+
+    #[test]
+    fn parse_string_long_decimal_exact() {
+        let fraction = BigFraction::new(BigInt::parse("12345678901234567890123").unwrap(), power_of_ten(23)).unwrap();
+        assert_eq!(parse_elems("0.12345678901234567890123"), Ok(vec![Elem::NumBigFraction(fraction)]));
     }
 
     #[test]
     fn parse_string_unacceptable_value() {
-        assert_eq!(parse_string(&"123a".to_string()), Err("Unsupported characters: a".to_string()));
+        assert_eq!(parse_string("123$"), Err(ParseError::new(ErrorKind::UnsupportedChar('$'), Span::new(3, 4))));
     }
 
     #[test]
     fn parse_string_int_maximum_plus() {
-        assert_eq!(parse_string(&"9223372036854775808".to_string()), Err("Conversion Failure".to_string()));
+        assert_eq!(parse_string("X = 9223372036854775808"), Ok(vec![
+            Token::new(Elem::X, 0, 1), Token::new(Elem::Equal, 2, 3),
+            Token::new(Elem::NumBig(BigInt::parse("9223372036854775808").unwrap()), 4, 23)]));
     }
 
     #[test]
     fn parse_string_two_dot() {
-        assert_eq!(parse_string(&"123..456".to_string()), Err("With multiple decimal places".to_string()));
+        assert_eq!(parse_string("123..456"), Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(0, 5))));
+    }
+
+    #[test]
+    fn parse_string_paren() {
+        use Elem::*;
+        assert_eq!(parse_elems("(X + 1)^2 = 3(X - 2)"), Ok(vec![
+            LeftParen, X, Plus, NumInt(1), RightParen, Power, NumInt(2), Equal,
+            NumInt(3), LeftParen, X, Minus, NumInt(2), RightParen]));
+    }
+
+    #[test]
+    fn parse_string_lowercase() {
+        use Elem::*;
+        assert_eq!(parse_elems("x^2 = 2X"), Ok(vec![X, Power, NumInt(2), Equal, NumInt(2), X]));
+    }
+
+    #[test]
+    fn parse_string_other_variable() {
+        assert_eq!(parse_string("2θ"), Ok(vec![Token::new(Elem::NumInt(2), 0, 1), Token::new(Elem::X, 1, 3)]));
+    }
+
+    #[test]
+    fn parse_string_mixed_variables() {
+        assert_eq!(parse_string("x + y = 1"), Err(ParseError::new(ErrorKind::MixedVariables('x', 'y'), Span::new(4, 5))));
+    }
+
+    #[test]
+    fn find_variable_first() {
+        let variable = |equation: &str| find_variable(equation, &parse_string(equation).unwrap());
+        assert_eq!((variable("2e3 = t^2 + T"), variable("1 = 2")), ('t', 'X'));
+    }
+
+    #[test]
+    fn parse_string_exponent() {
+        use Elem::*;
+        assert_eq!(parse_elems("1e-3 X = 2.5E4"), Ok(vec![
+            NumFraction(Fraction::safe_new(1, 1000).unwrap()), X, Equal, NumInt(25000)]));
+    }
+
+    #[test]
+    fn parse_string_exponent_span() {
+        assert_eq!(parse_string("1.5e+2e"), Ok(vec![Token::new(Elem::NumInt(150), 0, 6), Token::new(Elem::X, 6, 7)]));
+    }
+
+    #[test]
+    fn parse_string_exponent_letter() {
+        // With another letter, the e is the constant rather than the variable
+        assert_eq!(parse_elems("1e X"), Ok(vec![Elem::NumInt(1), Elem::Constant(Constant::E), Elem::X]));
+        assert_eq!(parse_elems("2e"), Ok(vec![Elem::NumInt(2), Elem::X]));
+    }
+
+    #[test]
+    fn parse_string_exponent_small_exact() {
+        let fraction = BigFraction::new(BigInt::from_i64(15), power_of_ten(31)).unwrap();
+        assert_eq!(parse_elems("1.5e-30"), Ok(vec![Elem::NumBigFraction(fraction)]));
+    }
+
+    #[test]
+    fn parse_string_exponent_underflow() {
+        assert_eq!(parse_string("1e-400 X = 1"), Err(ParseError::new(ErrorKind::NumberOutOfRange, Span::new(0, 6))));
+        assert_eq!(parse_string("2 = 1e2000"), Err(ParseError::new(ErrorKind::NumberOutOfRange, Span::new(4, 10))));
+        assert_eq!(parse_string("1e99999999999999999999"), Err(ParseError::new(ErrorKind::NumberOutOfRange, Span::new(0, 22))));
+        assert_eq!(parse_elems("0e-400"), Ok(vec![Elem::NumFraction(Fraction::safe_new(0, 1).unwrap())]));
+    }
+
+    #[test]
+    fn parse_string_e_constant() {
+        use Elem::*;
+        assert_eq!(parse_elems("2e - 3 = X"), Ok(vec![NumInt(2), Constant(crate::term::Constant::E), Minus, NumInt(3), Equal, X]));
+    }
+
+    #[test]
+    fn parse_string_e_variable() {
+        use Elem::*;
+        assert_eq!(parse_elems("e^2 = 4"), Ok(vec![X, Power, NumInt(2), Equal, NumInt(4)]));
+    }
+
+    #[test]
+    fn parse_string_e_clash() {
+        assert_eq!(parse_string("E + e = 1"), Err(ParseError::new(ErrorKind::VariableClash('E'), Span::new(4, 5))));
+    }
+
+    #[test]
+    fn parse_string_function() {
+        assert_eq!(parse_string("sqrt(2)X = π"), Ok(vec![
+            Token::new(Elem::Function(Function::Sqrt), 0, 4), Token::new(Elem::LeftParen, 4, 5), Token::new(Elem::NumInt(2), 5, 6),
+            Token::new(Elem::RightParen, 6, 7), Token::new(Elem::X, 7, 8), Token::new(Elem::Equal, 9, 10),
+            Token::new(Elem::Constant(Constant::Pi), 11, 13)]));
+    }
+
+    #[test]
+    fn parse_string_unknown_word() {
+        assert_eq!(parse_string("xpi"), Err(ParseError::new(ErrorKind::MixedVariables('x', 'p'), Span::new(1, 2))));
+    }
+
+    #[test]
+    fn parse_string_unknown_function() {
+        let error = ParseError::new(ErrorKind::UnknownFunction("log".to_string()), Span::new(4, 7));
+        assert_eq!(parse_string("X = log (0)"), Err(error));
+        assert_eq!(parse_elems("xX(2)"), Ok(vec![Elem::X, Elem::X, Elem::LeftParen, Elem::NumInt(2), Elem::RightParen]));
+    }
+
+    #[test]
+    fn parse_string_leading_dot() {
+        assert_eq!(parse_elems(".5X"), Ok(vec![Elem::NumFraction(Fraction::safe_new(1, 2).unwrap()), Elem::X]));
+    }
+
+    #[test]
+    fn parse_string_underscore() {
+        assert_eq!(parse_string("1_000_000 = X"), Ok(vec![
+            Token::new(Elem::NumInt(1_000_000), 0, 9), Token::new(Elem::Equal, 10, 11), Token::new(Elem::X, 12, 13)]));
+    }
+
+    #[test]
+    fn parse_string_underscore_malformed() {
+        assert_eq!(parse_string("X = 1__0"), Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(4, 6))));
+        assert_eq!(parse_string("1_.5 = X"), Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(0, 2))));
+    }
+
+    #[test]
+    fn parse_string_hex_binary() {
+        use Elem::*;
+        assert_eq!(parse_elems("0x1F X = 0b1_01"), Ok(vec![NumInt(31), X, Equal, NumInt(5)]));
+    }
+
+    #[test]
+    fn parse_string_hex_big() {
+        assert_eq!(parse_elems("0x10000000000000000"), Ok(vec![Elem::NumBig(BigInt::parse("18446744073709551616").unwrap())]));
+    }
+
+    #[test]
+    fn parse_string_binary_malformed() {
+        assert_eq!(parse_string("0b102"), Err(ParseError::new(ErrorKind::MalformedNumber, Span::new(0, 5))));
+    }
+
+    #[test]
+    fn parse_string_zero_x() {
+        assert_eq!(parse_elems("0x = 0"), Ok(vec![Elem::NumInt(0), Elem::X, Elem::Equal, Elem::NumInt(0)]));
     }
 }
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use computor_v1::term::{Constant, Function};
+
+use crate::line_editor::LineEditor;
+use crate::{Format, Output, solve_and_print, exit_on_write_error};
+
+
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+    // A single letter is left to be the variable of the equations, and constants and functions keep their meaning
+    let is_reserved = Constant::from_name(name).is_some() || Function::from_name(name).is_some();
+    name.len() > 1 && !is_reserved && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+
+fn split_assignment(line: &str) -> Result<(Option<String>, String), String> {
+    // name := equation, where an equation without = is taken to be equal to zero
+    let (name, body) = match line.split_once(":=") {
+        Some(v) => v,
+        None => return Ok((None, line.to_string())),
+    };
+    let name = name.trim();
+    if !is_name(name) {
+        return Err(format!("Invalid name: {}", name))
+    }
+    let body = body.trim();
+    if body.contains('=') {
+        Ok((Some(name.to_string()), body.to_string()))
+    } else {
+        Ok((Some(name.to_string()), format!("{} = 0", body)))
+    }
+}
+
+
+fn substitute(line: &str, variables: &HashMap<String, String>) -> String {
+    // Each stored name is replaced by its polynomial in parentheses
+    let mut string = String::new();
+    let mut word = String::new();
+    for c in line.chars().chain(std::iter::once('\0')) {
+        // A name starts with a letter, so the digits of 2p stay a number
+        if (c.is_ascii_alphanumeric() || c == '_') && (!word.is_empty() || !c.is_ascii_digit()) {
+            word.push(c);
+            continue
+        }
+        if !word.is_empty() {
+            match variables.get(&word) {
+                Some(expression) => string += &format!("({})", expression),
+                None => string += &word,
+            }
+            word.clear();
+        }
+        if c != '\0' {
+            string.push(c);
+        }
+    }
+    string
+}
+
+
+fn expression_string(equation: &str) -> String {
+    // The equation as typed with everything on the left, since the reduced form has its divisions by X cleared
+    match equation.split_once('=') {
+        Some((lhs, rhs)) if rhs.trim() == "0" => lhs.trim().to_string(),
+        Some((lhs, rhs)) => format!("({}) - ({})", lhs.trim(), rhs.trim()),
+        None => equation.to_string(),
+    }
+}
+
+
+pub fn run(output: Output) {
+    let mut editor = LineEditor::new();
+    let mut variables = HashMap::new();
+    // The prompt is left out when the input is piped
+    let prompt = if editor.is_terminal() {"> "} else {""};
+    loop {
+        let line = match editor.read_line(prompt) {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("{}", e);
+                break
+            },
+        };
+        if editor.is_terminal() {
+            editor.add_history(&line);
+        }
+        let line = line.trim();
+        match line {
+            "" => continue,
+            "exit" | "quit" => break,
+            _ => (),
+        }
+        let (name, equation) = match split_assignment(line) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("{}", e);
+                continue
+            },
+        };
+        let equation = substitute(&equation, &variables);
+        if let (Some(_), Some(name)) = (solve_and_print(output, &equation), name) {
+            if matches!(output.format, Format::Text | Format::Steps) {
+                exit_on_write_error(writeln!(io::stdout(), "Stored as {}", name));
+            }
+            variables.insert(name, expression_string(&equation));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_assignment_equation() {
+        assert_eq!(split_assignment("X^2 = 4"), Ok((None, "X^2 = 4".to_string())));
+    }
+
+    #[test]
+    fn split_assignment_expression() {
+        assert_eq!(split_assignment("p1 := X + 1"), Ok((Some("p1".to_string()), "X + 1 = 0".to_string())));
+    }
+
+    #[test]
+    fn split_assignment_invalid_name() {
+        assert_eq!(split_assignment("X := 1 = 0"), Err("Invalid name: X".to_string()));
+    }
+
+    #[test]
+    fn split_assignment_single_letter() {
+        assert_eq!(split_assignment("t := t + 1"), Err("Invalid name: t".to_string()));
+    }
+
+    #[test]
+    fn split_assignment_reserved_name() {
+        assert_eq!(split_assignment("pi := X + 1"), Err("Invalid name: pi".to_string()));
+        assert_eq!(split_assignment("sqrt := X"), Err("Invalid name: sqrt".to_string()));
+        assert!(["e", "π", "cbrt", "abs"].iter().all(|name| !is_name(name)));
+    }
+
+    #[test]
+    fn substitute_names() {
+        let mut variables = HashMap::new();
+        variables.insert("p".to_string(), "- 1 * X^0 + 1 * X^1".to_string());
+        assert_eq!(substitute("2p^2 + q = X", &variables), "2(- 1 * X^0 + 1 * X^1)^2 + q = X".to_string());
+    }
+
+    #[test]
+    fn expression_string_normal() {
+        assert_eq!(expression_string("X + 1 = 3"), "(X + 1) - (3)".to_string());
+        assert_eq!(expression_string("X + 1 = 0"), "X + 1".to_string());
+    }
+
+    #[test]
+    fn stored_division_by_variable() {
+        // pp := 1/X keeps its division, so that pp * X = 1 still excludes X = 0
+        let mut variables = HashMap::new();
+        let (_, equation) = split_assignment("pp := 1/X").unwrap();
+        variables.insert("pp".to_string(), expression_string(&equation));
+        let polynomial = computor_v1::reduce(&substitute("pp * X = 1", &variables)).unwrap();
+        assert!(polynomial.excludes_zero());
+        assert_eq!(computor_v1::solve(&polynomial), computor_v1::Solution::AllReals);
+    }
+}
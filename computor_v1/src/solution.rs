@@ -1,44 +1,134 @@
-use crate::term::{Term, Coefficient};
+use crate::term::{Term, Coefficient, MAX_DEGREE};
 use crate::fraction::Fraction;
-use crate::utility::is_int_value;
-use crate::math_utility::math_utility::sqrt;
+use crate::big_int::BigInt;
+use crate::big_fraction::BigFraction;
+use crate::complex::Complex;
+use crate::durand_kerner::{durand_kerner, error_bounds};
+use crate::utility::{is_int_value, lcm_of_bottoms};
+use crate::math_utility::math_utility::{sqrt, cbrt, cos, acos, divisors, checked_gcd, square_factor, PI};
 
 
-fn degree_0_solution_all() -> String {
-    "The solution is an arbitrary real number.".to_string()
+// Beyond the range of divisors, only the divisors up to this are tried for a rational root
+const SMALL_DIVISOR_LIMIT: i64 = 1000;
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Exact {
+    // top / bottom, irreducible with a positive bottom
+    Rational(BigInt, BigInt),
+    // (top + k√m) / bottom, or (top + ki√m) / bottom when imaginary
+    Radical {top: i64, k: i64, m: i64, bottom: i64, is_imaginary: bool},
+}
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Root {
+    pub value: Complex,
+    pub exact: Option<Exact>,
+    pub error: Option<f64>,
+    pub multiplicity: usize,
+}
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Solution {
+    NoSolution,
+    AllReals,
+    // The coefficients are beyond the range of f64, or the degree above MAX_DEGREE, so the roots could not be computed
+    Unsolvable,
+    Roots {degree: i64, discriminant: Option<Coefficient>, roots: Vec<Root>},
 }
 
 
-fn degree_0_solution_non() -> String {
-    "There is no solution.".to_string()
+impl Root {
+    fn decimal(value: f64) -> Root {
+        Root {value: Complex::new(value, 0.0), exact: None, error: None, multiplicity: 1}
+    }
+
+    fn complex(re: f64, im: f64) -> Root {
+        Root {value: Complex::new(re, im), exact: None, error: None, multiplicity: 1}
+    }
+
+    fn rational(top: &BigInt, bottom: &BigInt) -> Option<Root> {
+        if bottom.is_zero() {
+            return None
+        }
+        let gcd = top.gcd(bottom);
+        let (mut top, _) = top.div_rem(&gcd)?;
+        let (mut bottom, _) = bottom.div_rem(&gcd)?;
+        if bottom.is_negative() {
+            top = top.neg();
+            bottom = bottom.neg();
+        }
+        let value = BigFraction::new(top.clone(), bottom.clone())?.to_float();
+        Some(Root {
+            value: Complex::new(value, 0.0),
+            exact: Some(Exact::Rational(top, bottom)),
+            error: None,
+            multiplicity: 1,
+        })
+    }
+
+    fn fraction(fraction: &Fraction) -> Option<Root> {
+        Root::rational(&BigInt::from_i64(fraction.top()), &BigInt::from_i64(fraction.bottom()))
+    }
+
+    fn with_multiplicity(self, multiplicity: usize) -> Root {
+        Root {multiplicity, ..self}
+    }
+
+    pub fn is_real(&self) -> bool {
+        self.value.im == 0.0
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.exact.is_some()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match &self.exact {
+            Some(Exact::Rational(top, _)) => top.is_zero(),
+            Some(Exact::Radical {..}) => false,
+            None => self.value.re == 0.0 && self.value.im == 0.0,
+        }
+    }
 }
 
 
-fn degree_0_solution(terms: &Vec<Term>) -> String {
+fn degree_0_solution(terms: &[Term]) -> Solution {
     if terms.is_empty() {
-        degree_0_solution_all()
+        Solution::AllReals
     } else {
         if terms[0].coefficient.is_zero() {
-            degree_0_solution_all()
+            Solution::AllReals
         } else {
-            degree_0_solution_non()
+            Solution::NoSolution
         }
     }
 }
 
 
-fn check_all_int_terms(terms: &Vec<Term>) -> bool {
+fn check_all_int_terms(terms: &[Term]) -> bool {
     for term in terms {
         match term.coefficient {
-            Coefficient::NumInt(_) => {},
-            Coefficient::NumFloat(_) => return false,
+            Coefficient::NumInt(_) | Coefficient::NumBig(_) => {},
+            Coefficient::NumFloat(_) | Coefficient::NumFraction(_) | Coefficient::NumBigFraction(_) => return false,
         }
     }
     true
 }
 
 
-fn make_terms_no_gaps(terms: &Vec<Term>, degree: i64) -> Vec<Term> {
+pub fn is_degree_supported(terms: &[Term]) -> bool {
+    // Negative degrees count as well, since clearing them raises the others
+    let degrees = terms.iter().filter(|term| !term.coefficient.is_zero()).map(|term| term.degree);
+    let highest = degrees.clone().max().unwrap_or(0);
+    let lowest = degrees.min().unwrap_or(0).min(0);
+    matches!(highest.checked_sub(lowest), Some(range) if range <= MAX_DEGREE)
+}
+
+
+pub fn make_terms_no_gaps(terms: &[Term], degree: i64) -> Vec<Term> {
     let mut vec = Vec::new();
     let mut index = 0;
     for i in 0..=degree {
@@ -53,56 +143,33 @@ fn make_terms_no_gaps(terms: &Vec<Term>, degree: i64) -> Vec<Term> {
 }
 
 
-fn degree_1_solution_2_terms_fraction(terms: &Vec<Term>) -> Option<String> {
-    let b = match terms[0].coefficient {
-        Coefficient::NumInt(n) => {
-            match n.checked_neg() {
-                Some(x) => x,
-                None => return None,
-            }
-        },
-        _ => 0,
-    };
-    let a = match terms[1].coefficient {
-        Coefficient::NumInt(n) => n,
-        _ => 0,
-    };
-    let mut fraction = match Fraction::safe_new(b, a) {
-        Some(x) => x,
-        None => return None,
-    };
-    fraction = fraction.make_irreducible_fraction();
-    if b == 0 {
-        Some("0".to_string())
-    } else {
-        Some(format!("{}", fraction))
-    }
+fn degree_1_root_fraction(terms: &[Term]) -> Option<Root> {
+    let b = terms[0].coefficient.to_big_int()?.neg();
+    let a = terms[1].coefficient.to_big_int()?;
+    Root::rational(&b, &a)
 }
 
 
-fn degree_1_solution_2_terms_decimals(terms: &Vec<Term>) -> String {
+fn degree_1_root_decimals(terms: &[Term]) -> Root {
     let a = terms[1].coefficient.to_float();
     let b = - terms[0].coefficient.to_float();
-    format!("{}", b / a)
+    Root::decimal(b / a)
 }
 
 
-fn degree_1_solution(terms: &Vec<Term>) -> String {
+fn degree_1_solution(terms: &[Term]) -> Solution {
     // ax + b = 0
-    let string = "The solution is:\n".to_string();
     let terms = make_terms_no_gaps(terms, 1);
-    let is_all_int = check_all_int_terms(&terms);
-    if is_all_int {
-        match degree_1_solution_2_terms_fraction(&terms) {
-            Some(s) => return string + s.as_str(),
-            None => {},
-        }
+    let mut root = None;
+    if check_all_int_terms(&terms) {
+        root = degree_1_root_fraction(&terms);
     }
-    string + degree_1_solution_2_terms_decimals(&terms).as_str()
+    let root = root.unwrap_or_else(|| degree_1_root_decimals(&terms));
+    Solution::Roots {degree: 1, discriminant: None, roots: vec![root]}
 }
 
 
-fn degree_2_discriminant(terms: &Vec<Term>) -> Coefficient {
+pub fn degree_2_discriminant(terms: &[Term]) -> Coefficient {
     let c = &terms[0].coefficient;
     let b = &terms[1].coefficient;
     let a = &terms[2].coefficient;
@@ -111,99 +178,694 @@ fn degree_2_discriminant(terms: &Vec<Term>) -> Coefficient {
 }
 
 
-fn degree_2_solution_one_from_two_coefficient(a: &Coefficient, b: &Coefficient) -> String {
-    if let (Coefficient::NumInt(n1), Coefficient::NumInt(n2)) = (a, b) {
-        match Fraction::safe_new(*n2, *n1) {
-            Some(fraction) => {
-                let fraction = fraction.make_irreducible_fraction();
-                if *n1 == 0 {
-                    return "0".to_string()
-                } else {
-                    return format!("{}", fraction)
-                }
-            },
-            None => {},
-        };
+fn degree_2_root_from_two_coefficient(a: &Coefficient, b: &Coefficient) -> Root {
+    // b / a
+    if let (Some(n1), Some(n2)) = (a.to_big_int(), b.to_big_int()) {
+        if let Some(root) = Root::rational(&n2, &n1) {
+            return root
+        }
     }
     let a = a.to_float();
     let b = b.to_float();
-    format!("{}", b / a)
+    Root::decimal(b / a)
 }
 
 
-fn degree_2_solution_one(terms: &Vec<Term>) -> String {
-    let string = "Discriminant is zero, the solution is:\n".to_string();
+fn degree_2_roots_one(terms: &[Term]) -> Vec<Root> {
     let b = &terms[1].coefficient;
     let a = &terms[2].coefficient;
     let temp = Coefficient::NumInt(-2);
     let a = a.mul(&temp);
-    string + degree_2_solution_one_from_two_coefficient(&a, b).as_str()
+    vec![degree_2_root_from_two_coefficient(&a, b).with_multiplicity(2)]
+}
+
+
+fn radical_parts(terms: &[Term], discriminant: &Coefficient) -> Option<(i64, i64, i64, i64)> {
+    // (-b ± k√m) / 2a as (top, k, m, bottom), with the square factors of |discriminant| taken out of the root
+    let (b, a, discriminant) = match (&terms[1].coefficient, &terms[2].coefficient, discriminant) {
+        (Coefficient::NumInt(b), Coefficient::NumInt(a), Coefficient::NumInt(d)) => (*b, *a, *d),
+        _ => return None,
+    };
+    let (k, m) = square_factor(discriminant.checked_abs()?)?;
+    let mut top = b.checked_neg()?;
+    let mut k = k;
+    let mut bottom = a.checked_mul(2)?;
+    let gcd = checked_gcd(checked_gcd(top, k)?, bottom)?;
+    top /= gcd;
+    k /= gcd;
+    bottom /= gcd;
+    if bottom < 0 {
+        top = top.checked_neg()?;
+        k = -k;
+        bottom = -bottom;
+    }
+    Some((top, k, m, bottom))
+}
+
+
+fn degree_2_roots_radical(terms: &[Term], discriminant: &Coefficient) -> Option<Vec<Root>> {
+    let (top, k, m, bottom) = radical_parts(terms, discriminant)?;
+    if m == 1 {
+        return None
+    }
+    let mut vec = Vec::new();
+    for k in [k, -k] {
+        let value = (top as f64 + k as f64 * sqrt(m as f64)) / bottom as f64;
+        vec.push(Root {
+            value: Complex::new(value, 0.0),
+            exact: Some(Exact::Radical {top, k, m, bottom, is_imaginary: false}),
+            error: None,
+            multiplicity: 1,
+        });
+    }
+    Some(vec)
+}
+
+
+fn degree_2_roots_complex_exact(terms: &[Term], discriminant: &Coefficient) -> Option<Vec<Root>> {
+    let (top, k, m, bottom) = radical_parts(terms, discriminant)?;
+    let mut vec = Vec::new();
+    for k in [k.abs(), -k.abs()] {
+        let value = Complex::new(top as f64 / bottom as f64, k as f64 * sqrt(m as f64) / bottom as f64);
+        vec.push(Root {
+            value,
+            exact: Some(Exact::Radical {top, k, m, bottom, is_imaginary: true}),
+            error: None,
+            multiplicity: 1,
+        });
+    }
+    Some(vec)
 }
 
 
-fn degree_2_solution_two(terms: &Vec<Term>, discriminant: &Coefficient) -> String {
-    let mut string = "Discriminant is strictly positive, the two solutions are:\n".to_string();
+fn degree_2_roots_stable(terms: &[Term], discriminant_root: f64) -> Vec<Root> {
+    // -b ± √Δ cancels out when √Δ is close to b, so that root is taken as c / q with q = -(b + sign(b)√Δ) / 2
+    let c = terms[0].coefficient.to_float();
+    let b = terms[1].coefficient.to_float();
+    let a = terms[2].coefficient.to_float();
+    let q = -(b + b.signum() * discriminant_root) / 2.0;
+    let (plus, minus) = if b >= 0.0 {(c / q, q / a)} else {(q / a, c / q)};
+    // Rounding the coefficients moves a root r by up to EPS * (|a|r^2 + |b||r| + |c|) / |p'(r)|, where |p'(r)| = √Δ,
+    // and the formula itself adds a few roundings of r
+    let bound = |r: f64| 4.0 * f64::EPSILON * ((a.abs() * r * r + b.abs() * r.abs() + c.abs()) / discriminant_root + r.abs());
+    vec![
+        Root {error: Some(bound(plus)), ..Root::decimal(plus)},
+        Root {error: Some(bound(minus)), ..Root::decimal(minus)},
+    ]
+}
+
+
+fn degree_2_roots_two(terms: &[Term], discriminant: &Coefficient) -> Vec<Root> {
+    if let Some(roots) = degree_2_roots_radical(terms, discriminant) {
+        return roots
+    }
     let b = &terms[1].coefficient;
     let a = &terms[2].coefficient;
     let temp = Coefficient::NumInt(2);
     let a = a.mul(&temp);
     let discriminant_root = sqrt(discriminant.to_float());
-    let discriminant = if is_int_value(discriminant_root) {
-        Coefficient::NumInt(discriminant_root as i64)
-    } else {
-        Coefficient::NumFloat(discriminant_root)
+    let discriminant = match discriminant.to_big_int() {
+        // An integer discriminant is a perfect square only if its integer root squares back to it
+        Some(n) => match n.sqrt() {
+            Some(root) if root.mul(&root) == n => Coefficient::from_big_int(root),
+            _ => return degree_2_roots_stable(terms, discriminant_root),
+        },
+        None if is_int_value(discriminant_root) => Coefficient::NumInt(discriminant_root as i64),
+        None => return degree_2_roots_stable(terms, discriminant_root),
     };
     let b_plus = b.mul_minus().add(&discriminant);
     let b_minus = b.mul_minus().add(&discriminant.mul_minus());
-    string += degree_2_solution_one_from_two_coefficient(&a, &b_plus).as_str();
-    string += "\n";
-    string + degree_2_solution_one_from_two_coefficient(&a, &b_minus).as_str()
+    vec![
+        degree_2_root_from_two_coefficient(&a, &b_plus),
+        degree_2_root_from_two_coefficient(&a, &b_minus),
+    ]
+}
+
+
+fn complex_pair(re: f64, im: f64) -> Vec<Root> {
+    // The conjugate pair re ± im i
+    vec![Root::complex(re, im.abs()), Root::complex(re, -im.abs())]
 }
 
 
-fn degree_2_solution_complex(terms: &Vec<Term>, discriminant: &Coefficient) -> String {
-    let string = "Discriminant is strictly negative, the two complex solutions are:\n".to_string();
+fn degree_2_roots_complex(terms: &[Term], discriminant: &Coefficient) -> Vec<Root> {
+    if let Some(roots) = degree_2_roots_complex_exact(terms, discriminant) {
+        return roots
+    }
     let b = &terms[1].coefficient.to_float();
     let a = &terms[2].coefficient.to_float() * 2.0;
     let discriminant_root = sqrt(-discriminant.to_float());
     let real_num = -b / a;
     let complex_num = discriminant_root / a;
-    string + format!("{} ± {}i", real_num, complex_num).as_str()
+    complex_pair(real_num, complex_num)
+}
+
+
+fn degree_2_roots(terms: &[Term]) -> Vec<Root> {
+    let discriminant = degree_2_discriminant(terms);
+    if discriminant.is_zero() {
+        degree_2_roots_one(terms)
+    } else if discriminant.is_plus() {
+        degree_2_roots_two(terms, &discriminant)
+    } else {
+        degree_2_roots_complex(terms, &discriminant)
+    }
 }
 
 
-fn degree_2_solution(terms: &Vec<Term>) -> String {
+fn degree_2_solution(terms: &[Term]) -> Solution {
     // ax^2 + bx + c = 0
     let terms = make_terms_no_gaps(terms, 2);
     let discriminant = degree_2_discriminant(&terms);
-    if discriminant.is_zero() {
-        degree_2_solution_one(&terms)
+    let roots = degree_2_roots(&terms);
+    Solution::Roots {degree: 2, discriminant: Some(discriminant), roots}
+}
+
+
+pub fn degree_3_discriminant(terms: &[Term]) -> Coefficient {
+    // b^2c^2 - 4ac^3 - 4b^3d - 27a^2d^2 + 18abcd
+    let d = &terms[0].coefficient;
+    let c = &terms[1].coefficient;
+    let b = &terms[2].coefficient;
+    let a = &terms[3].coefficient;
+    let b2c2 = b.mul(b).mul(c).mul(c);
+    let ac3 = Coefficient::NumInt(-4).mul(a).mul(c).mul(c).mul(c);
+    let b3d = Coefficient::NumInt(-4).mul(b).mul(b).mul(b).mul(d);
+    let a2d2 = Coefficient::NumInt(-27).mul(a).mul(a).mul(d).mul(d);
+    let abcd = Coefficient::NumInt(18).mul(a).mul(b).mul(c).mul(d);
+    b2c2.add(&ac3).add(&b3d).add(&a2d2).add(&abcd)
+}
+
+
+fn int_coefficients(terms: &[Term]) -> Option<Vec<i64>> {
+    // None when a coefficient does not fit in i64
+    terms.iter().map(|term| match term.coefficient {
+        Coefficient::NumInt(n) => Some(n),
+        _ => None,
+    }).collect()
+}
+
+
+fn big_coefficients(terms: &[Term]) -> Option<Vec<BigInt>> {
+    // None when a coefficient is not an integer
+    terms.iter().map(|term| term.coefficient.to_big_int()).collect()
+}
+
+
+fn evaluate_int_polynomial(coefficients: &[i64], p: i64, q: i64) -> Option<i128> {
+    // q^n * f(p / q), computed with Horner's method
+    let mut value = 0_i128;
+    let mut q_power = 1_i128;
+    for (i, n) in coefficients.iter().rev().enumerate() {
+        if i > 0 {
+            q_power = q_power.checked_mul(q as i128)?;
+        }
+        value = value.checked_mul(p as i128)?.checked_add((*n as i128).checked_mul(q_power)?)?;
+    }
+    Some(value)
+}
+
+
+fn evaluate_big_polynomial(coefficients: &[BigInt], p: i64, q: i64) -> BigInt {
+    // The same as evaluate_int_polynomial, for when it overflows
+    let (p, q) = (BigInt::from_i64(p), BigInt::from_i64(q));
+    let mut value = BigInt::from_i64(0);
+    let mut q_power = BigInt::from_i64(1);
+    for (i, n) in coefficients.iter().rev().enumerate() {
+        if i > 0 {
+            q_power = q_power.mul(&q);
+        }
+        value = value.mul(&p).add(&n.mul(&q_power));
+    }
+    value
+}
+
+
+fn candidate_divisors(n: &BigInt) -> Option<Vec<i64>> {
+    // Every divisor when there are few enough to try, otherwise only the small ones
+    if let Some(vec) = n.to_i64().and_then(divisors) {
+        return Some(vec)
+    }
+    let vec: Vec<i64> = (1..=SMALL_DIVISOR_LIMIT)
+        .filter(|i| matches!(n.div_rem(&BigInt::from_i64(*i)), Some((_, rem)) if rem.is_zero()))
+        .collect();
+    if vec.is_empty() {None} else {Some(vec)}
+}
+
+
+fn rational_root(terms: &[Term]) -> Option<Fraction> {
+    // By the rational root theorem, p / q with p | constant term and q | leading coefficient
+    let coefficients = big_coefficients(terms)?;
+    let small_coefficients = int_coefficients(terms);
+    if coefficients[0].is_zero() {
+        return Fraction::safe_new(0, 1)
+    }
+    let tops = candidate_divisors(&coefficients[0])?;
+    let bottoms = candidate_divisors(&coefficients[coefficients.len() - 1])?;
+    for q in &bottoms {
+        for top in &tops {
+            for p in [*top, -*top] {
+                if checked_gcd(p, *q) != Some(1) {
+                    continue;
+                }
+                let value = small_coefficients.as_ref().and_then(|v| evaluate_int_polynomial(v, p, *q));
+                let is_root = match value {
+                    Some(value) => value == 0,
+                    None => evaluate_big_polynomial(&coefficients, p, *q).is_zero(),
+                };
+                if is_root {
+                    return Fraction::safe_new(p, *q)
+                }
+            }
+        }
+    }
+    None
+}
+
+
+fn deflate(terms: &[Term], root: &Fraction) -> Option<Vec<Term>> {
+    // Divide by (qx - p) for the root p / q, the quotient has integer coefficients by Gauss's lemma.
+    // None when p / q is not a root, so that a repeated root can be divided out until it is gone
+    let coefficients = big_coefficients(terms)?;
+    if coefficients.len() < 2 {
+        return None
+    }
+    let (p, q) = (BigInt::from_i64(root.top()), BigInt::from_i64(root.bottom()));
+    let mut quotient = vec![BigInt::from_i64(0); coefficients.len() - 1];
+    let mut carry = BigInt::from_i64(0);
+    for degree in (1..coefficients.len()).rev() {
+        let (value, rem) = coefficients[degree].add(&carry.mul(&p)).div_rem(&q)?;
+        if !rem.is_zero() {
+            return None
+        }
+        carry = value.clone();
+        quotient[degree - 1] = value;
+    }
+    if !coefficients[0].add(&carry.mul(&p)).is_zero() {
+        return None
+    }
+    Some(quotient.into_iter().enumerate()
+        .map(|(degree, value)| Term {coefficient: Coefficient::from_big_int(value), degree: degree as i64})
+        .collect())
+}
+
+
+fn unique_roots(roots: Vec<Root>) -> Vec<Root> {
+    // Equal roots are merged and their multiplicities added
+    let mut unique: Vec<Root> = Vec::new();
+    for root in roots {
+        match unique.iter_mut().find(|other| other.value == root.value && other.exact == root.exact) {
+            Some(other) => other.multiplicity += root.multiplicity,
+            None => unique.push(root),
+        }
+    }
+    unique
+}
+
+
+fn degree_3_roots_fraction(terms: &[Term]) -> Option<Vec<Root>> {
+    let root = rational_root(terms)?;
+    let quadratic = deflate(terms, &root)?;
+    let mut vec = vec![Root::fraction(&root)?];
+    vec.extend(degree_2_roots(&quadratic));
+    Some(vec)
+}
+
+
+fn degree_3_roots_decimals(terms: &[Term], discriminant: &Coefficient) -> Vec<Root> {
+    // x = t - b / 3 turns the equation divided by a into t^3 + pt + q = 0
+    let a = terms[3].coefficient.to_float();
+    let d = terms[0].coefficient.to_float() / a;
+    let c = terms[1].coefficient.to_float() / a;
+    let b = terms[2].coefficient.to_float() / a;
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let shift = -b / 3.0;
+    // t = ku, with k a power of two of the size of the roots, keeps q^2 and p^3 in range
+    let k = power_of_two_below(sqrt(p.abs()).max(cbrt(q.abs())));
+    let (p, q) = (p / k / k, q / k / k / k);
+    let vec = if discriminant.is_zero() {
+        if p == 0.0 {
+            vec![Root::decimal(0.0).with_multiplicity(3)]
+        } else {
+            vec![
+                Root::decimal(3.0 * q / p),
+                Root::decimal(-3.0 * q / (2.0 * p)).with_multiplicity(2),
+            ]
+        }
     } else if discriminant.is_plus() {
-        degree_2_solution_two(&terms, &discriminant)
+        let m = 2.0 * sqrt(-p / 3.0);
+        let theta = acos(3.0 * q / (p * m)) / 3.0;
+        let mut values: Vec<f64> = (0..3).map(|k| m * cos(theta - 2.0 * PI * k as f64 / 3.0)).collect();
+        // A root much smaller than m cancels out in the cosine, so it is taken from the product of the roots, -q
+        let smallest = (0..3).min_by(|i, j| values[*i].abs().total_cmp(&values[*j].abs())).unwrap_or(0);
+        let others: f64 = (0..3).filter(|i| *i != smallest).map(|i| values[i]).product();
+        if values[smallest].abs() < m / 4.0 && others != 0.0 {
+            values[smallest] = -q / others;
+        }
+        values.into_iter().map(Root::decimal).collect()
+    } else {
+        // The real root is u + v, the complex ones -(u + v) / 2 ± i(√3 / 2)(u - v)
+        let (u, v) = degree_3_cardano_parts(p, q);
+        let real = degree_3_largest_real_root(p, q);
+        let mut vec = vec![Root::decimal(real)];
+        vec.extend(complex_pair(-real / 2.0, sqrt(3.0) / 2.0 * (u - v).abs()));
+        vec
+    };
+    scale_roots(vec, k, shift)
+}
+
+
+fn degree_3_roots(terms: &[Term]) -> Vec<Root> {
+    let discriminant = degree_3_discriminant(terms);
+    let mut roots = None;
+    if check_all_int_terms(terms) {
+        roots = degree_3_roots_fraction(terms);
+    }
+    match roots {
+        Some(v) => v,
+        None => degree_3_roots_decimals(terms, &discriminant),
+    }
+}
+
+
+fn degree_3_solution(terms: &[Term]) -> Solution {
+    // ax^3 + bx^2 + cx + d = 0
+    let terms = make_terms_no_gaps(terms, 3);
+    let discriminant = degree_3_discriminant(&terms);
+    let roots = unique_roots(degree_3_roots(&terms));
+    Solution::Roots {degree: 3, discriminant: Some(discriminant), roots}
+}
+
+
+fn degree_3_cardano_parts(p: f64, q: f64) -> (f64, f64) {
+    // t^3 + pt + q = 0 with q^2 / 4 + p^3 / 27 >= 0 has the real root u + v, where u is the cube root of
+    // the larger of -q / 2 ± √(q^2 / 4 + p^3 / 27) and uv = -p / 3
+    let s = sqrt(q * q / 4.0 + p * p * p / 27.0);
+    let u = cbrt(-q / 2.0 - if q < 0.0 {-s} else {s});
+    (u, if u == 0.0 {0.0} else {-p / (3.0 * u)})
+}
+
+
+fn degree_3_largest_real_root(p: f64, q: f64) -> f64 {
+    // t^3 + pt + q = 0
+    let s = q * q / 4.0 + p * p * p / 27.0;
+    if s < 0.0 {
+        let m = 2.0 * sqrt(-p / 3.0);
+        m * cos(acos(3.0 * q / (p * m)) / 3.0)
+    } else {
+        // When p > 0, u + v cancels out, so it is taken as -q / (u^2 - uv + v^2) from u^3 + v^3 = -q
+        let (u, v) = degree_3_cardano_parts(p, q);
+        if p > 0.0 {-q / (u * u - u * v + v * v)} else {u + v}
+    }
+}
+
+
+fn quadratic_roots_decimals(b: f64, c: f64) -> Vec<Root> {
+    // x^2 + bx + c = 0
+    let discriminant = b * b - 4.0 * c;
+    if discriminant < 0.0 {
+        let complex_num = sqrt(-discriminant) / 2.0;
+        complex_pair(-b / 2.0, complex_num)
     } else {
-        degree_2_solution_complex(&terms, &discriminant)
+        let root = sqrt(discriminant);
+        vec![
+            Root::decimal((-b + root) / 2.0),
+            Root::decimal((-b - root) / 2.0),
+        ]
+    }
+}
+
+
+fn degree_4_roots_fraction(terms: &[Term]) -> Option<Vec<Root>> {
+    let root = rational_root(terms)?;
+    let cubic = deflate(terms, &root)?;
+    let mut vec = vec![Root::fraction(&root)?];
+    vec.extend(degree_3_roots(&cubic));
+    Some(vec)
+}
+
+
+fn power_of_two_below(v: f64) -> f64 {
+    // The power of two at or below v, or 1 when v is zero or out of the normal range
+    if v < f64::MIN_POSITIVE || !v.is_finite() {
+        return 1.0
+    }
+    f64::from_bits(v.to_bits() & (0x7ff << 52))
+}
+
+
+fn scale_roots(roots: Vec<Root>, k: f64, shift: f64) -> Vec<Root> {
+    // Each root y becomes ky + shift
+    roots.into_iter().map(|root| Root {value: Complex::new(root.value.re * k + shift, root.value.im * k), ..root}).collect()
+}
+
+
+fn degree_4_roots_decimals(terms: &[Term]) -> Vec<Root> {
+    // x = y - b / 4 turns the equation divided by a into y^4 + py^2 + qy + r = 0
+    let a = terms[4].coefficient.to_float();
+    let e = terms[0].coefficient.to_float() / a;
+    let d = terms[1].coefficient.to_float() / a;
+    let c = terms[2].coefficient.to_float() / a;
+    let b = terms[3].coefficient.to_float() / a;
+    let p = c - 3.0 * b * b / 8.0;
+    let q = b * b * b / 8.0 - b * c / 2.0 + d;
+    let r = -3.0 * b * b * b * b / 256.0 + b * b * c / 16.0 - b * d / 4.0 + e;
+    let shift = -b / 4.0;
+    // y = ku, with k a power of two of the size of the roots, keeps the powers of p, q and r in range
+    let k = power_of_two_below(sqrt(p.abs()).max(cbrt(q.abs())).max(sqrt(sqrt(r.abs()))));
+    let (p, q, r) = (p / k / k, q / k / k / k, r / k / k / k / k);
+    if q == 0.0 {
+        return scale_roots(degree_4_roots_biquadratic(p, r), k, shift)
+    }
+    // Resolvent cubic m^3 + pm^2 + (p^2 / 4 - r)m - q^2 / 8 = 0 always has a positive root
+    let resolvent_p = -p * p / 12.0 - r;
+    let resolvent_q = -p * p * p / 108.0 + p * r / 3.0 - q * q / 8.0;
+    let m = degree_3_largest_real_root(resolvent_p, resolvent_q) - p / 3.0;
+    let s = sqrt(2.0 * m);
+    let mut vec = quadratic_roots_decimals(-s, p / 2.0 + m + q / (2.0 * s));
+    vec.extend(quadratic_roots_decimals(s, p / 2.0 + m - q / (2.0 * s)));
+    scale_roots(vec, k, shift)
+}
+
+
+fn degree_4_roots_biquadratic(p: f64, r: f64) -> Vec<Root> {
+    // y^4 + py^2 + r = 0 is a quadratic in z = y^2
+    let discriminant = p * p - 4.0 * r;
+    if discriminant < 0.0 {
+        // y = ±(u ± vi), the square roots of z = (-p ± sqrt(discriminant)) / 2
+        let re = -p / 2.0;
+        let im = sqrt(-discriminant) / 2.0;
+        let abs = Complex::new(re, im).abs();
+        let u = sqrt((abs + re) / 2.0);
+        let v = sqrt((abs - re) / 2.0);
+        let mut vec = complex_pair(u, v);
+        vec.extend(complex_pair(-u, v));
+        return vec
+    }
+    let mut vec = Vec::new();
+    let root = sqrt(discriminant);
+    for z in [(-p + root) / 2.0, (-p - root) / 2.0] {
+        if z < 0.0 {
+            vec.extend(complex_pair(0.0, sqrt(-z)));
+        } else {
+            vec.push(Root::decimal(sqrt(z)));
+            vec.push(Root::decimal(-sqrt(z)));
+        }
+    }
+    vec
+}
+
+
+fn degree_4_solution(terms: &[Term]) -> Solution {
+    // ax^4 + bx^3 + cx^2 + dx + e = 0
+    let terms = make_terms_no_gaps(terms, 4);
+    let mut roots = None;
+    if check_all_int_terms(&terms) {
+        roots = degree_4_roots_fraction(&terms);
+    }
+    let roots = unique_roots(match roots {
+        Some(v) => v,
+        None => degree_4_roots_decimals(&terms),
+    });
+    Solution::Roots {degree: 4, discriminant: None, roots}
+}
+
+
+fn durand_kerner_roots(terms: &[Term]) -> Option<Vec<Root>> {
+    let coefficients: Vec<f64> = terms.iter().map(|term| term.coefficient.to_float()).collect();
+    // The iteration keeps its guesses finite, so only the coefficients can be out of range
+    if coefficients.iter().any(|n| !n.is_finite()) {
+        return None
+    }
+    let roots = durand_kerner(&coefficients);
+    let bounds = error_bounds(&coefficients, &roots);
+    let mut reals = Vec::new();
+    let mut complexes = Vec::new();
+    for (root, bound) in roots.iter().zip(bounds) {
+        // A real part lost in rounding noise is printed as zero
+        let noise = bound.min(f64::EPSILON * root.abs().max(1.0));
+        let re = if root.re.abs() <= noise {0.0} else {root.re};
+        if root.im.abs() <= bound {
+            reals.push((re, bound));
+        } else {
+            complexes.push((re, root.im, bound));
+        }
+    }
+    // The roots come in conjugate pairs, unless the iteration did not converge and every guess is kept
+    let is_paired = complexes.iter().filter(|c| c.1 > 0.0).count() * 2 == complexes.len();
+    if is_paired {
+        complexes.retain(|c| c.1 > 0.0);
+    }
+    let mut vec = Vec::new();
+    for (re, bound) in &reals {
+        vec.push(Root {error: Some(*bound), ..Root::decimal(*re)});
+    }
+    for (re, im, bound) in &complexes {
+        let roots = if is_paired {complex_pair(*re, *im)} else {vec![Root::complex(*re, *im)]};
+        for root in roots {
+            vec.push(Root {error: Some(*bound), ..root});
+        }
+    }
+    Some(vec)
+}
+
+
+fn degree_n_solution(terms: &[Term], degree: i64) -> Solution {
+    let mut terms = make_terms_no_gaps(terms, degree);
+    // Zero and rational roots are divided out exactly, so that only the rest is approximated
+    let mut roots = Vec::new();
+    let zeros = terms.iter().position(|term| !term.coefficient.is_zero()).unwrap_or(0);
+    if zeros > 0 {
+        roots.extend(Root::rational(&BigInt::from_i64(0), &BigInt::from_i64(1)).map(|root| root.with_multiplicity(zeros)));
+        terms = terms[zeros..].iter().map(|term| Term {coefficient: term.coefficient.clone(), degree: term.degree - zeros as i64}).collect();
+    }
+    while terms.len() > 5 {
+        let root = match rational_root(&terms) {
+            Some(root) => root,
+            None => break,
+        };
+        let mut multiplicity = 0;
+        while let Some(quotient) = deflate(&terms, &root) {
+            multiplicity += 1;
+            terms = quotient;
+        }
+        match Root::fraction(&root) {
+            Some(value) if multiplicity > 0 => roots.push(value.with_multiplicity(multiplicity)),
+            _ => break,
+        }
+    }
+    let remaining = terms.len() as i64 - 1;
+    if remaining > 4 {
+        match durand_kerner_roots(&terms) {
+            Some(v) => roots.extend(v),
+            None => return Solution::Unsolvable,
+        }
+    } else if let Solution::Roots {roots: v, ..} = degree_solution(&terms, remaining) {
+        roots.extend(v);
+    }
+    Solution::Roots {degree, discriminant: None, roots: unique_roots(roots)}
+}
+
+
+pub fn clear_denominators(terms: &[Term]) -> Option<Vec<Term>> {
+    // Multiplying by the lcm of the bottoms keeps the roots and makes every coefficient an integer
+    let lcm = Coefficient::from_big_int(lcm_of_bottoms(terms)?);
+    Some(terms.iter().map(|term| Term {coefficient: term.coefficient.mul(&lcm), degree: term.degree}).collect())
+}
+
+
+pub fn clear_negative_degrees(terms: &[Term]) -> Option<Vec<Term>> {
+    // Multiplying by X^k, with -k the lowest degree, leaves the same equation without negative degrees
+    if terms.iter().all(|term| term.degree >= 0) {
+        return None
+    }
+    let lowest = terms.iter().filter(|term| !term.coefficient.is_zero()).map(|term| term.degree).min().unwrap_or(0).min(0);
+    Some(terms.iter()
+        .filter(|term| term.degree >= lowest)
+        .map(|term| Term {coefficient: term.coefficient.clone(), degree: term.degree - lowest})
+        .collect())
+}
+
+
+pub fn exclude_zero(solution: Solution) -> Solution {
+    // X = 0 is not a solution of an equation that divides by X, even when the cleared equation has it
+    match solution {
+        Solution::Roots {degree, discriminant, mut roots} => {
+            let count = roots.len();
+            roots.retain(|root| !root.is_zero());
+            if roots.is_empty() {
+                Solution::NoSolution
+            } else if roots.len() < count {
+                // The discriminant counted the zero root, so it no longer describes the solutions
+                Solution::Roots {degree, discriminant: None, roots}
+            } else {
+                Solution::Roots {degree, discriminant, roots}
+            }
+        },
+        other => other,
     }
 }
 
 
-pub fn solution(terms: &Vec<Term>, degree: i64) -> String {
+fn sort_roots(roots: &mut [Root]) {
+    // Real roots first, largest first, then complex roots by their real part, largest first, with +i before -i.
+    // The order depends on the roots only, so that X^2 - 1 = 0 and 1 - X^2 = 0 list theirs alike
+    roots.sort_by(|a, b| {
+        b.is_real().cmp(&a.is_real())
+            .then(b.value.re.total_cmp(&a.value.re))
+            .then(a.value.im.abs().total_cmp(&b.value.im.abs()))
+            .then(b.value.im.total_cmp(&a.value.im))
+    });
+}
+
+
+fn degree_solution(terms: &[Term], degree: i64) -> Solution {
     match degree {
-        0 => degree_0_solution(&terms),
-        1 => degree_1_solution(&terms),
-        2 => {degree_2_solution(&terms)},
-        _ => {"".to_string()}
+        0 => degree_0_solution(terms),
+        1 => degree_1_solution(terms),
+        2 => degree_2_solution(terms),
+        3 => degree_3_solution(terms),
+        4 => degree_4_solution(terms),
+        _ => degree_n_solution(terms, degree),
+    }
+}
+
+
+pub fn solution(terms: &[Term], degree: i64) -> Solution {
+    if !is_degree_supported(terms) {
+        return Solution::Unsolvable
+    }
+    let cleared = clear_denominators(terms).unwrap_or(terms.to_vec());
+    let mut solution = degree_solution(&cleared, degree);
+    // An exact root is kept even when its decimal value overflows
+    if let Solution::Roots {discriminant, roots, ..} = &mut solution {
+        // Clearing the denominators scales the discriminant, so it is that of the equation as given
+        if discriminant.is_some() {
+            *discriminant = match degree {
+                2 => Some(degree_2_discriminant(&make_terms_no_gaps(terms, 2))),
+                _ => Some(degree_3_discriminant(&make_terms_no_gaps(terms, 3))),
+            };
+        }
+        sort_roots(roots);
+        let is_finite = |root: &Root| root.value.re.is_finite() && root.value.im.is_finite();
+        if roots.is_empty() || roots.iter().any(|root| !root.is_exact() && !is_finite(root)) {
+            return Solution::Unsolvable
+        }
     }
+    solution
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution_string::make_solution_string;
 
     #[test]
     fn degree_0_solution_empty() {
         let terms = Vec::new();
-        assert_eq!(degree_0_solution(&terms), "The solution is an arbitrary real number.".to_string());
+        assert_eq!(make_solution_string(&degree_0_solution(&terms)), "The solution is an arbitrary real number.".to_string());
     }
 
     #[test]
@@ -211,7 +873,7 @@ mod tests {
         let terms = vec![
             Term {coefficient: Coefficient::NumInt(0), degree: 0},
         ];
-        assert_eq!(degree_0_solution(&terms), "The solution is an arbitrary real number.".to_string());
+        assert_eq!(make_solution_string(&degree_0_solution(&terms)), "The solution is an arbitrary real number.".to_string());
     }
 
     #[test]
@@ -219,7 +881,7 @@ mod tests {
         let terms = vec![
             Term {coefficient: Coefficient::NumInt(1), degree: 0},
         ];
-        assert_eq!(degree_0_solution(&terms), "There is no solution.".to_string());
+        assert_eq!(make_solution_string(&degree_0_solution(&terms)), "There is no solution.".to_string());
     }
 
     #[test]
@@ -228,7 +890,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(0), degree: 0},
             Term {coefficient: Coefficient::NumInt(1), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n0".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n0".to_string());
     }
 
     #[test]
@@ -237,7 +899,25 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(1), degree: 0},
             Term {coefficient: Coefficient::NumInt(1), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n-1".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n-1".to_string());
+    }
+
+    #[test]
+    fn degree_1_solution_a_big() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-6), degree: 0},
+            Term {coefficient: Coefficient::NumBig(BigInt::parse("100000000000000000000").unwrap()), degree: 1},
+        ];
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n3 / 50000000000000000000".to_string());
+    }
+
+    #[test]
+    fn degree_1_solution_b_int_minimum() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(i64::MIN), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 1},
+        ];
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n9223372036854775808".to_string());
     }
 
     #[test]
@@ -246,7 +926,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(1), degree: 0},
             Term {coefficient: Coefficient::NumInt(2), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n-1 / 2".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n-1 / 2".to_string());
     }
 
     #[test]
@@ -255,7 +935,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(1), degree: 0},
             Term {coefficient: Coefficient::NumInt(-2), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n1 / 2".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n1 / 2".to_string());
     }
 
     #[test]
@@ -264,7 +944,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(2), degree: 0},
             Term {coefficient: Coefficient::NumInt(1), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n-2".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n-2".to_string());
     }
 
     #[test]
@@ -273,7 +953,7 @@ mod tests {
             Term {coefficient: Coefficient::NumFloat(2.0), degree: 0},
             Term {coefficient: Coefficient::NumInt(1), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n-2".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n-2".to_string());
     }
 
     #[test]
@@ -282,7 +962,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(1), degree: 0},
             Term {coefficient: Coefficient::NumFloat(2.0), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n-0.5".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n-0.5".to_string());
     }
 
     #[test]
@@ -291,7 +971,7 @@ mod tests {
             Term {coefficient: Coefficient::NumFloat(1.0), degree: 0},
             Term {coefficient: Coefficient::NumFloat(2.0), degree: 1},
         ];
-        assert_eq!(degree_1_solution(&terms), "The solution is:\n-0.5".to_string());
+        assert_eq!(make_solution_string(&degree_1_solution(&terms)), "The solution is:\n-0.5".to_string());
     }
 
     #[test]
@@ -351,8 +1031,8 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(0), degree: 1},
             Term {coefficient: Coefficient::NumInt(-1), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is zero, the solution is:\n0".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is zero, the solution is:\n0 (multiplicity 2)".to_string());
     }
 
     #[test]
@@ -362,8 +1042,8 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(0), degree: 1},
             Term {coefficient: Coefficient::NumInt(-1), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is strictly positive, the two solutions are:\n-1\n1".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly positive, the two solutions are:\n1\n-1".to_string());
     }
 
     #[test]
@@ -373,8 +1053,8 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(0), degree: 1},
             Term {coefficient: Coefficient::NumInt(1), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is strictly negative, the two complex solutions are:\n-0 ± 1i".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly negative, the two complex solutions are:\ni\n-i".to_string());
     }
 
     #[test]
@@ -384,8 +1064,8 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(4), degree: 1},
             Term {coefficient: Coefficient::NumInt(5), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is strictly negative, the two complex solutions are:\n-0.4 ± 0.2i".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly negative, the two complex solutions are:\n(-2 + i) / 5\n(-2 - i) / 5".to_string());
     }
 
     #[test]
@@ -395,8 +1075,8 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(-2), degree: 1},
             Term {coefficient: Coefficient::NumInt(1), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is zero, the solution is:\n1".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is zero, the solution is:\n1 (multiplicity 2)".to_string());
     }
 
     #[test]
@@ -406,8 +1086,8 @@ mod tests {
             Term {coefficient: Coefficient::NumFloat(-2.4), degree: 1},
             Term {coefficient: Coefficient::NumFloat(1.2), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is zero, the solution is:\n1".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is zero, the solution is:\n1 (multiplicity 2)".to_string());
     }
 
     #[test]
@@ -417,8 +1097,8 @@ mod tests {
             Term {coefficient: Coefficient::NumFloat(-3.0), degree: 1},
             Term {coefficient: Coefficient::NumInt(1), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
-            "Discriminant is zero, the solution is:\n1.5".to_string());
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is zero, the solution is:\n1.5 (multiplicity 2)".to_string());
     }
 
     #[test]
@@ -428,7 +1108,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(-3), degree: 1},
             Term {coefficient: Coefficient::NumInt(1), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
             "Discriminant is strictly positive, the two solutions are:\n2\n1".to_string());
     }
 
@@ -439,7 +1119,7 @@ mod tests {
             Term {coefficient: Coefficient::NumInt(1), degree: 1},
             Term {coefficient: Coefficient::NumInt(2), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
             "Discriminant is strictly positive, the two solutions are:\n1\n-3 / 2".to_string());
     }
 
@@ -450,7 +1130,532 @@ mod tests {
             Term {coefficient: Coefficient::NumFloat(1.0), degree: 1},
             Term {coefficient: Coefficient::NumInt(2), degree: 2},
         ];
-        assert_eq!(degree_2_solution(&terms),
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
             "Discriminant is strictly positive, the two solutions are:\n1\n-1.5".to_string());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn degree_2_solution_two_radical() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-11), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 1},
+            Term {coefficient: Coefficient::NumInt(2), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly positive, the two solutions are:\n(-1 + √89) / 4 ≈ 2.108495283014151\n(-1 - √89) / 4 ≈ -2.608495283014151".to_string());
+    }
+
+    #[test]
+    fn degree_2_solution_two_radical_square_factor() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-11), degree: 0},
+            Term {coefficient: Coefficient::NumInt(2), degree: 1},
+            Term {coefficient: Coefficient::NumInt(4), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly positive, the two solutions are:\n(-1 + 3√5) / 4 ≈ 1.4270509831248424\n(-1 - 3√5) / 4 ≈ -1.9270509831248424".to_string());
+    }
+
+    #[test]
+    fn degree_2_solution_two_radical_negative_a() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(3), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(-1), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly positive, the two solutions are:\n√3 ≈ 1.7320508075688772\n-√3 ≈ -1.7320508075688772".to_string());
+    }
+
+    #[test]
+    fn degree_2_solution_complex_radical() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(3), degree: 0},
+            Term {coefficient: Coefficient::NumInt(2), degree: 1},
+            Term {coefficient: Coefficient::NumInt(2), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly negative, the two complex solutions are:\n(-1 + i√5) / 2 ≈ -0.5 + 1.118033988749895i\n(-1 - i√5) / 2 ≈ -0.5 - 1.118033988749895i".to_string());
+    }
+
+    #[test]
+    fn degree_2_solution_complex_float_no_minus_zero() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFloat(-0.25), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumFloat(-1.0), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly negative, the two complex solutions are:\n0 + 0.5i\n0 - 0.5i".to_string());
+    }
+
+    #[test]
+    fn degree_2_discriminant_big() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(i64::MAX), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ];
+        assert_eq!(degree_2_discriminant(&terms),
+            Coefficient::NumBig(BigInt::parse("85070591730234615847396907784232501245").unwrap()));
+    }
+
+    #[test]
+    fn degree_2_solution_two_big() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumBig(BigInt::parse("-85070591730234615865843651857942052864").unwrap()), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly positive, the two solutions are:\n9223372036854775808\n-9223372036854775808".to_string());
+    }
+
+    #[test]
+    fn degree_2_solution_two_big_not_square() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(3), degree: 1},
+            Term {coefficient: Coefficient::NumBig(BigInt::parse("100000000000000000000").unwrap()), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 2)),
+            "Discriminant is strictly positive, the two solutions are:\n0.000000000099999999985 (error < 1.8e-25)\n-0.000000000100000000015 (error < 1.8e-25)".to_string());
+    }
+
+    #[test]
+    fn degree_2_solution_two_big_cancellation() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(i64::MAX), degree: 1},
+            Term {coefficient: Coefficient::NumInt(i64::MAX), degree: 2},
+        ];
+        let roots = match degree_2_solution(&terms) {
+            Solution::Roots {roots, ..} => roots,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert!((roots[0].value.re + 1.0842021724855044e-19).abs() < 1e-33);
+        assert!(roots.iter().all(|root| !root.is_exact() && root.error.is_some()));
+    }
+
+    #[test]
+    fn degree_3_discriminant_all_int() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-6), degree: 0},
+            Term {coefficient: Coefficient::NumInt(11), degree: 1},
+            Term {coefficient: Coefficient::NumInt(-6), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(degree_3_discriminant(&terms), Coefficient::NumInt(4));
+    }
+
+    #[test]
+    fn degree_3_discriminant_only_ad() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(0), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(degree_3_discriminant(&terms), Coefficient::NumInt(-27));
+    }
+
+    #[test]
+    fn rational_root_zero() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(0), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-2), degree: 1},
+            Term {coefficient: Coefficient::NumInt(0), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(rational_root(&terms), Fraction::safe_new(0, 1));
+    }
+
+    #[test]
+    fn rational_root_fraction() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(0), degree: 2},
+            Term {coefficient: Coefficient::NumInt(8), degree: 3},
+        ];
+        assert_eq!(rational_root(&terms), Fraction::safe_new(1, 2));
+    }
+
+    #[test]
+    fn rational_root_none() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(0), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(rational_root(&terms), None);
+    }
+
+    #[test]
+    fn degree_3_solution_three_int() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-6), degree: 0},
+            Term {coefficient: Coefficient::NumInt(11), degree: 1},
+            Term {coefficient: Coefficient::NumInt(-6), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is strictly positive, the three solutions are:\n3\n2\n1".to_string());
+    }
+
+    #[test]
+    fn degree_3_solution_fraction_discriminant() {
+        // -4 * 1/2 * (-1/2)^3 = 1/4 for X^3/2 - X/2 = 0, where the cleared X^3 - X = 0 gives 4
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(-1, 2).unwrap()), degree: 1},
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap()), degree: 3},
+        ];
+        match solution(&terms, 3) {
+            Solution::Roots {discriminant, ..} => assert_eq!(discriminant, Some(Coefficient::NumFraction(Fraction::safe_new(1, 4).unwrap()))),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn degree_3_solution_three_fraction() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 1},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 2},
+            Term {coefficient: Coefficient::NumInt(2), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is strictly positive, the three solutions are:\n2\n1 / 2\n-1".to_string());
+    }
+
+    #[test]
+    fn degree_3_solution_double() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is zero, the two solutions are:\n1 (multiplicity 2)\n-2".to_string());
+    }
+
+    #[test]
+    fn degree_3_solution_triple() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(0), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is zero, the solution is:\n0 (multiplicity 3)".to_string());
+    }
+
+    #[test]
+    fn degree_3_solution_complex() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is strictly negative, the real solution and the two complex solutions are:\n-1\n(1 + i√3) / 2 ≈ 0.5 + 0.8660254037844386i\n(1 - i√3) / 2 ≈ 0.5 - 0.8660254037844386i".to_string());
+    }
+
+    #[test]
+    fn degree_3_solution_float_three() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFloat(6.5), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-7), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is strictly positive, the three solutions are:\n1.8842260208547013\n1.1404981454897947\n-3.0247241663444946".to_string());
+    }
+
+    #[test]
+    fn degree_3_solution_float_complex() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFloat(-2.0), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 3)),
+            "Discriminant is strictly negative, the real solution and the two complex solutions are:\n1.2599210498948732\n-0.6299605249474366 + 1.0911236359717214i\n-0.6299605249474366 - 1.0911236359717214i".to_string());
+    }
+
+    #[test]
+    fn deflate_cubic() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 1},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 2},
+            Term {coefficient: Coefficient::NumInt(2), degree: 3},
+        ];
+        assert_eq!(deflate(&terms, &Fraction::safe_new(1, 2).unwrap()), Some(vec![
+            Term {coefficient: Coefficient::NumInt(-2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-1), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ]));
+    }
+
+    #[test]
+    fn degree_4_solution_four_int() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(9), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-10), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 4},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 4)),
+            "The four solutions are:\n3\n1\n-1\n-3".to_string());
+    }
+
+    #[test]
+    fn degree_4_solution_double() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-2), degree: 2},
+            Term {coefficient: Coefficient::NumInt(1), degree: 4},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 4)),
+            "The two solutions are:\n1 (multiplicity 2)\n-1 (multiplicity 2)".to_string());
+    }
+
+    #[test]
+    fn degree_4_solution_only_a() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(0), degree: 0},
+            Term {coefficient: Coefficient::NumInt(3), degree: 4},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 4)),
+            "The solution is:\n0 (multiplicity 4)".to_string());
+    }
+
+    #[test]
+    fn degree_4_solution_complex() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 4},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 4)),
+            "The four complex solutions are:\n0.7071067811865476 + 0.7071067811865476i\n0.7071067811865476 - 0.7071067811865476i\n-0.7071067811865476 + 0.7071067811865476i\n-0.7071067811865476 - 0.7071067811865476i".to_string());
+    }
+
+    #[test]
+    fn degree_4_solution_ferrari() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumFloat(-3.0), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+            Term {coefficient: Coefficient::NumInt(2), degree: 4},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 4)),
+            "The two real solutions and the two complex solutions are:\n0.8212751345414412\n0.36011097309408935\n-0.8406930538177653 + 0.9918927313559953i\n-0.8406930538177653 - 0.9918927313559953i".to_string());
+    }
+
+    #[test]
+    fn degree_4_solution_real_and_complex_int() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 4},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 4)),
+            "The two real solutions and the two complex solutions are:\n1\n-1\ni\n-i".to_string());
+    }
+
+    #[test]
+    fn degree_n_solution_roots_of_unity() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 6},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 6)),
+            "The solutions are approximated with the Durand-Kerner method.\nThe six complex solutions are:\n0.8660254037844386 + 0.5i (error < 9.4e-16)\n0.8660254037844386 - 0.5i (error < 9.4e-16)\n0 + 1i (error < 4.4e-16)\n0 - 1i (error < 4.4e-16)\n-0.8660254037844386 + 0.49999999999999994i (error < 8.9e-16)\n-0.8660254037844386 - 0.49999999999999994i (error < 8.9e-16)".to_string());
+    }
+
+    #[test]
+    fn degree_n_solution_real_and_complex() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-6), degree: 0},
+            Term {coefficient: Coefficient::NumInt(11), degree: 1},
+            Term {coefficient: Coefficient::NumInt(-12), degree: 2},
+            Term {coefficient: Coefficient::NumInt(12), degree: 3},
+            Term {coefficient: Coefficient::NumInt(-6), degree: 4},
+            Term {coefficient: Coefficient::NumInt(1), degree: 5},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 5)),
+            "The three real solutions and the two complex solutions are:\n3\n2\n1\ni\n-i".to_string());
+    }
+
+    #[test]
+    fn degree_n_solution_high_degree() {
+        // The product of 119 distances between the guesses is beyond the range of f64
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 120},
+        ];
+        let roots = match solution(&terms, 120) {
+            Solution::Roots {roots, ..} => roots,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(roots.len(), 120);
+        assert!(roots.iter().all(|root| (root.value.abs() - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn sort_roots_order() {
+        let mut roots = vec![
+            Root::complex(0.0, -1.0),
+            Root::decimal(-1.0),
+            Root::complex(1.0, 2.0),
+            Root::decimal(2.0),
+            Root::complex(0.0, 1.0),
+            Root::complex(1.0, -2.0),
+        ];
+        sort_roots(&mut roots);
+        let values: Vec<(f64, f64)> = roots.iter().map(|root| (root.value.re, root.value.im)).collect();
+        assert_eq!(values, vec![(2.0, 0.0), (-1.0, 0.0), (1.0, 2.0), (1.0, -2.0), (0.0, 1.0), (0.0, -1.0)]);
+    }
+
+    #[test]
+    fn clear_denominators_fraction() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(-3, 10).unwrap()), degree: 0},
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 4).unwrap()), degree: 1},
+        ];
+        assert_eq!(clear_denominators(&terms), Some(vec![
+            Term {coefficient: Coefficient::NumInt(-6), degree: 0},
+            Term {coefficient: Coefficient::NumInt(5), degree: 1},
+        ]));
+    }
+
+    #[test]
+    fn clear_denominators_float() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(-3, 10).unwrap()), degree: 0},
+            Term {coefficient: Coefficient::NumFloat(0.5), degree: 1},
+        ];
+        assert_eq!(clear_denominators(&terms), None);
+    }
+
+    #[test]
+    fn solution_fraction_exact() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(-3, 10).unwrap()), degree: 0},
+            Term {coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 10).unwrap()), degree: 1},
+        ];
+        assert_eq!(make_solution_string(&solution(&terms, 1)), "The solution is:\n3".to_string());
+    }
+
+    #[test]
+    fn solution_double_root_multiplicity() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(2), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ];
+        assert_eq!(solution(&terms, 2), Solution::Roots {
+            degree: 2,
+            discriminant: Some(Coefficient::NumInt(0)),
+            roots: vec![Root {
+                value: Complex::new(-1.0, 0.0),
+                exact: Some(Exact::Rational(BigInt::from_i64(-1), BigInt::from_i64(1))),
+                error: None,
+                multiplicity: 2,
+            }],
+        });
+    }
+
+    #[test]
+    fn solution_cubic_merged_multiplicity() {
+        // (x - 1)^2 (x + 2)
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-3), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 3},
+        ];
+        let roots = match solution(&terms, 3) {
+            Solution::Roots {roots, ..} => roots,
+            other => panic!("unexpected {:?}", other),
+        };
+        let multiplicities: Vec<(f64, usize)> = roots.iter().map(|root| (root.value.re, root.multiplicity)).collect();
+        assert_eq!(multiplicities, vec![(1.0, 2), (-2.0, 1)]);
+    }
+
+    #[test]
+    fn solution_complex_exact() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ];
+        let roots = match solution(&terms, 2) {
+            Solution::Roots {roots, ..} => roots,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert!(roots.iter().all(|root| !root.is_real() && root.is_exact()));
+        assert_eq!(roots[1].exact, Some(Exact::Radical {top: -1, k: -1, m: 3, bottom: 2, is_imaginary: true}));
+    }
+
+    #[test]
+    fn solution_durand_kerner_approximate() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(-2), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 5},
+        ];
+        let roots = match solution(&terms, 5) {
+            Solution::Roots {roots, ..} => roots,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(roots.len(), 5);
+        assert!(roots.iter().all(|root| !root.is_exact() && root.error.is_some()));
+    }
+
+    #[test]
+    fn solution_no_solution() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(1), degree: 0},
+        ];
+        assert_eq!(solution(&terms, 0), Solution::NoSolution);
+    }
+
+    #[test]
+    fn solution_unsolvable() {
+        let huge = BigInt::parse(&format!("1{}", "0".repeat(400))).unwrap();
+        let terms = vec![
+            Term {coefficient: Coefficient::NumBig(huge), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 5},
+        ];
+        assert_eq!(solution(&terms, 5), Solution::Unsolvable);
+    }
+
+    #[test]
+    fn solution_exact_beyond_float() {
+        let huge = BigInt::parse(&format!("-1{}", "0".repeat(400))).unwrap();
+        let terms = vec![
+            Term {coefficient: Coefficient::NumBig(huge.clone()), degree: 0},
+            Term {coefficient: Coefficient::NumInt(1), degree: 1},
+        ];
+        let exact = match solution(&terms, 1) {
+            Solution::Roots {roots, ..} => roots[0].exact.clone(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(exact, Some(Exact::Rational(huge.neg(), BigInt::from_i64(1))));
+    }
+
+    #[test]
+    fn exclude_zero_roots() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(0), degree: 0},
+            Term {coefficient: Coefficient::NumInt(-1), degree: 1},
+            Term {coefficient: Coefficient::NumInt(1), degree: 2},
+        ];
+        assert_eq!(make_solution_string(&exclude_zero(solution(&terms, 2))), "The solution is:\n1".to_string());
+    }
+
+    #[test]
+    fn exclude_zero_only_root() {
+        let terms = vec![
+            Term {coefficient: Coefficient::NumInt(0), degree: 0},
+            Term {coefficient: Coefficient::NumInt(3), degree: 1},
+        ];
+        assert_eq!(exclude_zero(solution(&terms, 1)), Solution::NoSolution);
+    }
+}
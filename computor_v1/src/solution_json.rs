@@ -0,0 +1,183 @@
+use crate::Polynomial;
+use crate::error::ParseError;
+use crate::solution::{Solution, Root};
+use crate::solution_string::{float_string, make_decimal_string, make_exact_string};
+use crate::term::Coefficient;
+
+
+fn json_string(s: &str) -> String {
+    let mut string = "\"".to_string();
+    for c in s.chars() {
+        match c {
+            '"' => string += "\\\"",
+            '\\' => string += "\\\\",
+            '\n' => string += "\\n",
+            '\r' => string += "\\r",
+            '\t' => string += "\\t",
+            c if (c as u32) < 0x20 => string += &format!("\\u{:04x}", c as u32),
+            c => string.push(c),
+        }
+    }
+    string + "\""
+}
+
+
+fn json_number(value: f64) -> String {
+    // JSON has no representation for infinity and NaN
+    if value.is_finite() {
+        float_string(value)
+    } else {
+        "null".to_string()
+    }
+}
+
+
+fn coefficient_string(coefficient: &Coefficient) -> String {
+    match coefficient {
+        Coefficient::NumInt(n) => format!("{}", n),
+        Coefficient::NumBig(n) => format!("{}", n),
+        Coefficient::NumFloat(n) => float_string(*n),
+        Coefficient::NumFraction(f) => format!("{}", f),
+        Coefficient::NumBigFraction(f) => format!("{}", f),
+    }
+}
+
+
+fn discriminant_json(discriminant: &Option<Coefficient>) -> String {
+    match discriminant {
+        Some(discriminant) => format!("{{\"exact\":{},\"decimal\":{}}}",
+            json_string(&coefficient_string(discriminant)), json_number(discriminant.to_float())),
+        None => "null".to_string(),
+    }
+}
+
+
+fn classification(solution: &Solution) -> &'static str {
+    match solution {
+        Solution::NoSolution => "no_solution",
+        Solution::AllReals => "all_reals",
+        Solution::Unsolvable => "unsolvable",
+        Solution::Roots {roots, ..} => {
+            let real_count = roots.iter().filter(|root| root.is_real()).count();
+            if real_count == roots.len() {
+                "real"
+            } else if real_count == 0 {
+                "complex"
+            } else {
+                "real_and_complex"
+            }
+        },
+    }
+}
+
+
+fn root_json(root: &Root) -> String {
+    let exact = match &root.exact {
+        Some(exact) => json_string(&make_exact_string(exact)),
+        None => "null".to_string(),
+    };
+    let error = match root.error {
+        // Error bounds are tiny, so they are written in exponent notation
+        Some(bound) if bound.is_finite() => format!("{:e}", bound),
+        _ => "null".to_string(),
+    };
+    format!("{{\"exact\":{},\"decimal\":{},\"re\":{},\"im\":{},\"multiplicity\":{},\"error\":{}}}",
+        exact,
+        json_string(&make_decimal_string(root)),
+        json_number(root.value.re),
+        json_number(root.value.im),
+        root.multiplicity,
+        error)
+}
+
+
+pub fn make_solution_json(polynomial: &Polynomial, solution: &Solution) -> String {
+    let (discriminant, roots) = match solution {
+        Solution::Roots {discriminant, roots, ..} => (discriminant_json(discriminant), roots.as_slice()),
+        _ => ("null".to_string(), &[][..]),
+    };
+    let roots_json: Vec<String> = roots.iter().map(root_json).collect();
+    format!("{{\"variable\":{},\"reduced_form\":{},\"degree\":{},\"excludes_zero\":{},\"discriminant\":{},\"classification\":{},\"roots\":[{}]}}",
+        json_string(&polynomial.variable().to_string()),
+        json_string(&polynomial.reduced_form()),
+        polynomial.degree(),
+        polynomial.excludes_zero(),
+        discriminant,
+        json_string(classification(solution)),
+        roots_json.join(","))
+}
+
+
+pub fn make_error_json(error: &ParseError) -> String {
+    format!("{{\"error\":{{\"message\":{},\"start\":{},\"end\":{}}}}}",
+        json_string(&format!("{}", error)), error.span.start, error.span.end)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reduce, solve};
+    use crate::error::{ErrorKind, Span};
+
+    fn solution_json(equation: &str) -> String {
+        let polynomial = reduce(equation).unwrap();
+        make_solution_json(&polynomial, &solve(&polynomial))
+    }
+
+    #[test]
+    fn json_string_escape() {
+        assert_eq!(json_string("a\"b\\c\n√"), "\"a\\\"b\\\\c\\n√\"".to_string());
+    }
+
+    #[test]
+    fn json_number_infinite() {
+        assert_eq!(json_number(f64::INFINITY), "null".to_string());
+    }
+
+    #[test]
+    fn make_solution_json_rational() {
+        assert_eq!(solution_json("-3 + X + 2X^2 = 0"), concat!(
+            "{\"variable\":\"X\",\"reduced_form\":\"- 3 * X^0 + 1 * X^1 + 2 * X^2 = 0\",\"degree\":2,\"excludes_zero\":false,",
+            "\"discriminant\":{\"exact\":\"25\",\"decimal\":25},\"classification\":\"real\",\"roots\":[",
+            "{\"exact\":\"1\",\"decimal\":\"1\",\"re\":1,\"im\":0,\"multiplicity\":1,\"error\":null},",
+            "{\"exact\":\"-3 / 2\",\"decimal\":\"-1.5\",\"re\":-1.5,\"im\":0,\"multiplicity\":1,\"error\":null}]}",
+        ).to_string());
+    }
+
+    #[test]
+    fn make_solution_json_complex() {
+        assert_eq!(solution_json("1 + 4X + 5X^2 = 0"), concat!(
+            "{\"variable\":\"X\",\"reduced_form\":\"1 * X^0 + 4 * X^1 + 5 * X^2 = 0\",\"degree\":2,\"excludes_zero\":false,",
+            "\"discriminant\":{\"exact\":\"-4\",\"decimal\":-4},\"classification\":\"complex\",\"roots\":[",
+            "{\"exact\":\"(-2 + i) / 5\",\"decimal\":\"-0.4 + 0.2i\",\"re\":-0.4,\"im\":0.2,\"multiplicity\":1,\"error\":null},",
+            "{\"exact\":\"(-2 - i) / 5\",\"decimal\":\"-0.4 - 0.2i\",\"re\":-0.4,\"im\":-0.2,\"multiplicity\":1,\"error\":null}]}",
+        ).to_string());
+    }
+
+    #[test]
+    fn make_solution_json_fraction_discriminant() {
+        // 0^2 - 4 * 1/2 * -3, not the discriminant of the cleared X^2 - 6 = 0
+        assert!(solution_json("X^2/2 - 3 = 0").contains("\"discriminant\":{\"exact\":\"6\",\"decimal\":6}"));
+    }
+
+    #[test]
+    fn make_solution_json_all_reals() {
+        assert_eq!(solution_json("X = X"), concat!(
+            "{\"variable\":\"X\",\"reduced_form\":\"0 * X^1 = 0\",\"degree\":0,\"excludes_zero\":false,",
+            "\"discriminant\":null,\"classification\":\"all_reals\",\"roots\":[]}",
+        ).to_string());
+    }
+
+    #[test]
+    fn make_solution_json_variable() {
+        assert!(solution_json("y = 1").starts_with("{\"variable\":\"y\",\"reduced_form\":\"- 1 * y^0 + 1 * y^1 = 0\","));
+    }
+
+    #[test]
+    fn make_error_json_normal() {
+        let error = ParseError::new(ErrorKind::MisplacedOperator, Span::new(2, 3));
+        assert_eq!(make_error_json(&error),
+            "{\"error\":{\"message\":\"Misplaced operator\",\"start\":2,\"end\":3}}".to_string());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::Polynomial;
+use crate::big_int::BigInt;
+use crate::fraction::Fraction;
+use crate::solution::{Solution, Root, Exact};
+use crate::solution_string::{float_string, make_decimal_string};
+use crate::term::{Term, Coefficient};
+
+
+fn frac_latex(top: &str, bottom: &str) -> String {
+    format!("\\frac{{{}}}{{{}}}", top, bottom)
+}
+
+
+fn fraction_latex(fraction: &Fraction) -> String {
+    if fraction.bottom() == 1 {
+        format!("{}", fraction.top())
+    } else if fraction.top() < 0 {
+        format!("-{}", frac_latex(&format!("{}", fraction.top().unsigned_abs()), &format!("{}", fraction.bottom())))
+    } else {
+        frac_latex(&format!("{}", fraction.top()), &format!("{}", fraction.bottom()))
+    }
+}
+
+
+fn coefficient_latex(coefficient: &Coefficient) -> String {
+    match coefficient {
+        Coefficient::NumInt(n) => format!("{}", n),
+        Coefficient::NumBig(n) => format!("{}", n),
+        Coefficient::NumFloat(n) => float_string(*n),
+        // Decimals typed by the user are written as fractions too, which is how LaTeX shows exact values
+        Coefficient::NumFraction(f) => fraction_latex(f),
+        Coefficient::NumBigFraction(f) => rational_latex(f.top(), f.bottom()),
+    }
+}
+
+
+fn variable_latex(variable: char) -> char {
+    // The usual X is written x, as in mathematics, other variables as they were typed
+    if variable == 'X' {'x'} else {variable}
+}
+
+
+fn monomial_latex(value: &str, variable: char, degree: i64) -> String {
+    // 3, 3 x and 3 x^{2}, without the coefficient when it is 1
+    let power = match degree {
+        0 => return value.to_string(),
+        1 => variable.to_string(),
+        _ => format!("{}^{{{}}}", variable, degree),
+    };
+    if value == "1" {power} else {format!("{} {}", value, power)}
+}
+
+
+pub fn make_reduced_form_latex(terms: &[Term], variable: char) -> String {
+    let variable = variable_latex(variable);
+    let mut string = String::new();
+    for term in terms {
+        let value = coefficient_latex(&term.coefficient);
+        string += &if let Some(v) = value.strip_prefix('-') {
+            format!("- {} ", monomial_latex(v, variable, term.degree))
+        } else if string.is_empty() {
+            format!("{} ", monomial_latex(&value, variable, term.degree))
+        } else {
+            format!("+ {} ", monomial_latex(&value, variable, term.degree))
+        };
+    }
+    if string.is_empty() {
+        string += "0 ";
+    }
+    string += "= 0";
+    string
+}
+
+
+fn rational_latex(top: &BigInt, bottom: &BigInt) -> String {
+    if bottom.to_i64() == Some(1) {
+        format!("{}", top)
+    } else if top.is_negative() {
+        format!("-{}", frac_latex(&format!("{}", top.neg()), &format!("{}", bottom)))
+    } else {
+        frac_latex(&format!("{}", top), &format!("{}", bottom))
+    }
+}
+
+
+fn radical_latex(top: i64, k: i64, m: i64, bottom: i64, is_imaginary: bool) -> String {
+    // Same shape as radical_string, with the division written as a fraction
+    let radical = match (is_imaginary, k.abs(), m) {
+        (false, 1, _) => format!("\\sqrt{{{}}}", m),
+        (false, n, _) => format!("{}\\sqrt{{{}}}", n, m),
+        (true, 1, 1) => "i".to_string(),
+        (true, n, 1) => format!("{}i", n),
+        (true, 1, _) => format!("i\\sqrt{{{}}}", m),
+        (true, n, _) => format!("{}i\\sqrt{{{}}}", n, m),
+    };
+    let sign = if k < 0 {"-"} else {""};
+    if top == 0 && bottom == 1 {
+        format!("{}{}", sign, radical)
+    } else if top == 0 {
+        format!("{}{}", sign, frac_latex(&radical, &format!("{}", bottom)))
+    } else {
+        let numerator = format!("{} {} {}", top, if k < 0 {"-"} else {"+"}, radical);
+        if bottom == 1 {numerator} else {frac_latex(&numerator, &format!("{}", bottom))}
+    }
+}
+
+
+fn exact_latex(exact: &Exact) -> String {
+    match exact {
+        Exact::Rational(top, bottom) => rational_latex(top, bottom),
+        Exact::Radical {top, k, m, bottom, is_imaginary} => radical_latex(*top, *k, *m, *bottom, *is_imaginary),
+    }
+}
+
+
+fn root_latex(root: &Root) -> String {
+    let string = match &root.exact {
+        None => format!("\\approx {}", make_decimal_string(root)),
+        Some(exact @ Exact::Radical {m, ..}) if *m != 1 => {
+            format!("= {} \\approx {}", exact_latex(exact), make_decimal_string(root))
+        },
+        Some(exact) => format!("= {}", exact_latex(exact)),
+    };
+    let string = if root.multiplicity > 1 {
+        format!("{} \\quad (\\text{{multiplicity }} {})", string, root.multiplicity)
+    } else {
+        string
+    };
+    match root.error {
+        Some(bound) if bound.is_finite() => {
+            let mantissa_exponent = format!("{:.1e}", bound);
+            let (mantissa, exponent) = mantissa_exponent.split_once('e').unwrap_or((&mantissa_exponent, "0"));
+            format!("{} \\quad (\\text{{error}} < {} \\times 10^{{{}}})", string, mantissa, exponent)
+        },
+        Some(_) => format!("{} \\quad (\\text{{error unknown}})", string),
+        None => string,
+    }
+}
+
+
+pub fn make_solution_latex(polynomial: &Polynomial, solution: &Solution) -> String {
+    let variable = variable_latex(polynomial.variable());
+    let string = match solution {
+        Solution::NoSolution => format!("{} \\in \\emptyset", variable),
+        Solution::AllReals => format!("{} \\in \\mathbb{{R}}", variable),
+        Solution::Unsolvable => "\\text{unsolvable}".to_string(),
+        Solution::Roots {discriminant, roots, ..} => {
+            let mut lines = Vec::new();
+            if let Some(discriminant) = discriminant {
+                lines.push(format!("\\Delta = {}", coefficient_latex(discriminant)));
+            }
+            for (i, root) in roots.iter().enumerate() {
+                if roots.len() == 1 {
+                    lines.push(format!("{} {}", variable, root_latex(root)));
+                } else {
+                    lines.push(format!("{}_{{{}}} {}", variable, i + 1, root_latex(root)));
+                }
+            }
+            lines.join("\n")
+        },
+    };
+    if polynomial.excludes_zero() {
+        format!("{}\n{} \\neq 0", string, variable)
+    } else {
+        string
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reduce, solve};
+
+    fn solution_latex(equation: &str) -> String {
+        let polynomial = reduce(equation).unwrap();
+        make_solution_latex(&polynomial, &solve(&polynomial))
+    }
+
+    #[test]
+    fn make_reduced_form_latex_normal() {
+        let polynomial = reduce("5 * X^0 + 4 * X^1 - 9.3 * X^2 = 1 * X^0").unwrap();
+        assert_eq!(make_reduced_form_latex(polynomial.terms(), polynomial.variable()), "4 + 4 x - \\frac{93}{10} x^{2} = 0".to_string());
+    }
+
+    #[test]
+    fn make_reduced_form_latex_unit_coefficients() {
+        let polynomial = reduce("X^3 - X = 1").unwrap();
+        assert_eq!(make_reduced_form_latex(polynomial.terms(), polynomial.variable()), "- 1 - x + x^{3} = 0".to_string());
+    }
+
+    #[test]
+    fn make_reduced_form_latex_empty() {
+        assert_eq!(make_reduced_form_latex(&[], 'X'), "0 = 0".to_string());
+    }
+
+    #[test]
+    fn coefficient_latex_fraction() {
+        let fraction = Fraction::safe_new(-1, 3).unwrap();
+        assert_eq!(coefficient_latex(&Coefficient::NumFraction(fraction)), "-\\frac{1}{3}".to_string());
+    }
+
+    #[test]
+    fn radical_latex_imaginary() {
+        assert_eq!(radical_latex(-1, -2, 3, 5, true), "\\frac{-1 - 2i\\sqrt{3}}{5}".to_string());
+    }
+
+    #[test]
+    fn radical_latex_no_top() {
+        assert_eq!(radical_latex(0, -3, 5, 4, false), "-\\frac{3\\sqrt{5}}{4}".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_rational() {
+        assert_eq!(solution_latex("-3 + X + 2X^2 = 0"), "\\Delta = 25\nx_{1} = 1\nx_{2} = -\\frac{3}{2}".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_fraction_discriminant() {
+        // The discriminant of the equation as given, not that of the cleared X^2 - 6 = 0
+        assert_eq!(solution_latex("0.5 X^2 - 3 = 0"),
+            "\\Delta = 6\nx_{1} = \\sqrt{6} \\approx 2.449489742783178\nx_{2} = -\\sqrt{6} \\approx -2.449489742783178".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_fraction() {
+        assert_eq!(solution_latex("4X = 2.5"), "x = \\frac{5}{8}".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_radical() {
+        assert_eq!(solution_latex("X^2 = 2"),
+            "\\Delta = 8\nx_{1} = \\sqrt{2} \\approx 1.4142135623730951\nx_{2} = -\\sqrt{2} \\approx -1.4142135623730951".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_complex() {
+        assert_eq!(solution_latex("1 + 4X + 5X^2 = 0"),
+            "\\Delta = -4\nx_{1} = \\frac{-2 + i}{5}\nx_{2} = \\frac{-2 - i}{5}".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_error_bound() {
+        let root = Root {value: crate::complex::Complex::new(1.0, 0.0), exact: None, error: Some(4.4e-16), multiplicity: 1};
+        assert_eq!(root_latex(&root), "\\approx 1 \\quad (\\text{error} < 4.4 \\times 10^{-16})".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_multiplicity() {
+        assert_eq!(solution_latex("(X - 1)^2 = 0"), "\\Delta = 0\nx = 1 \\quad (\\text{multiplicity } 2)".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_variable() {
+        assert_eq!(solution_latex("2y = 1"), "y = \\frac{1}{2}".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_excluded_zero() {
+        assert_eq!(solution_latex("X^-1 = 2"), "x = \\frac{1}{2}\nx \\neq 0".to_string());
+    }
+
+    #[test]
+    fn make_solution_latex_all_reals() {
+        assert_eq!(solution_latex("X = X"), "x \\in \\mathbb{R}".to_string());
+    }
+}
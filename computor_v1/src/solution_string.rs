@@ -0,0 +1,266 @@
+use std::fmt;
+
+use crate::solution::{Solution, Root, Exact};
+use crate::term::Coefficient;
+
+
+fn number_to_word(n: usize) -> String {
+    match n {
+        1 => "one",
+        2 => "two",
+        3 => "three",
+        4 => "four",
+        5 => "five",
+        6 => "six",
+        7 => "seven",
+        8 => "eight",
+        9 => "nine",
+        10 => "ten",
+        _ => return format!("{}", n),
+    }.to_string()
+}
+
+
+fn roots_header(real_count: usize, complex_count: usize) -> String {
+    match (real_count, complex_count) {
+        (1, 0) => "The solution is:\n".to_string(),
+        (n, 0) => format!("The {} solutions are:\n", number_to_word(n)),
+        (0, m) => format!("The {} complex solutions are:\n", number_to_word(m)),
+        (1, m) => format!("The real solution and the {} complex solutions are:\n", number_to_word(m)),
+        (n, m) => format!("The {} real solutions and the {} complex solutions are:\n",
+            number_to_word(n), number_to_word(m)),
+    }
+}
+
+
+fn degree_2_header(discriminant: &Coefficient) -> String {
+    if discriminant.is_zero() {
+        "Discriminant is zero, the solution is:\n"
+    } else if discriminant.is_plus() {
+        "Discriminant is strictly positive, the two solutions are:\n"
+    } else {
+        "Discriminant is strictly negative, the two complex solutions are:\n"
+    }.to_string()
+}
+
+
+fn degree_3_header(discriminant: &Coefficient, root_count: usize) -> String {
+    if discriminant.is_zero() {
+        if root_count == 1 {
+            "Discriminant is zero, the solution is:\n"
+        } else {
+            "Discriminant is zero, the two solutions are:\n"
+        }
+    } else if discriminant.is_plus() {
+        "Discriminant is strictly positive, the three solutions are:\n"
+    } else {
+        "Discriminant is strictly negative, the real solution and the two complex solutions are:\n"
+    }.to_string()
+}
+
+
+fn make_header_string(degree: i64, discriminant: &Option<Coefficient>, roots: &[Root]) -> String {
+    let real_count = roots.iter().filter(|root| root.is_real()).count();
+    let complex_count = roots.len() - real_count;
+    match (degree, discriminant) {
+        (2, Some(discriminant)) => degree_2_header(discriminant),
+        (3, Some(discriminant)) => degree_3_header(discriminant, roots.len()),
+        // Above the fourth degree, only the roots left after the rational ones are approximated
+        _ if degree <= 4 || roots.iter().all(|root| root.error.is_none()) => roots_header(real_count, complex_count),
+        _ => "The solutions are approximated with the Durand-Kerner method.\n".to_string()
+            + roots_header(real_count, complex_count).as_str(),
+    }
+}
+
+
+pub fn float_string(value: f64) -> String {
+    // -0 is printed as 0
+    format!("{}", if value == 0.0 {0.0} else {value})
+}
+
+
+fn complex_string(re: f64, im: f64) -> String {
+    if im < 0.0 {
+        format!("{} - {}i", float_string(re), float_string(-im))
+    } else {
+        format!("{} + {}i", float_string(re), float_string(im))
+    }
+}
+
+
+fn radical_string(top: i64, k: i64, m: i64, bottom: i64, is_imaginary: bool) -> String {
+    // (top + k√m) / bottom, or (top + ki√m) / bottom, with the parts that are zero or one left out
+    let radical = match (is_imaginary, k.abs(), m) {
+        (false, 1, _) => format!("√{}", m),
+        (false, n, _) => format!("{}√{}", n, m),
+        (true, 1, 1) => "i".to_string(),
+        (true, n, 1) => format!("{}i", n),
+        (true, 1, _) => format!("i√{}", m),
+        (true, n, _) => format!("{}i√{}", n, m),
+    };
+    let numerator = if top == 0 {
+        format!("{}{}", if k < 0 {"-"} else {""}, radical)
+    } else {
+        format!("{} {} {}", top, if k < 0 {"-"} else {"+"}, radical)
+    };
+    if bottom == 1 {
+        numerator
+    } else if top == 0 {
+        format!("{} / {}", numerator, bottom)
+    } else {
+        format!("({}) / {}", numerator, bottom)
+    }
+}
+
+
+pub fn make_decimal_string(root: &Root) -> String {
+    if root.is_real() {
+        float_string(root.value.re)
+    } else {
+        complex_string(root.value.re, root.value.im)
+    }
+}
+
+
+pub fn make_exact_string(exact: &Exact) -> String {
+    match exact {
+        Exact::Rational(top, bottom) => {
+            if bottom.to_i64() == Some(1) {
+                format!("{}", top)
+            } else {
+                format!("{} / {}", top, bottom)
+            }
+        },
+        Exact::Radical {top, k, m, bottom, is_imaginary} => radical_string(*top, *k, *m, *bottom, *is_imaginary),
+    }
+}
+
+
+pub fn make_root_string(root: &Root) -> String {
+    let string = match &root.exact {
+        None => make_decimal_string(root),
+        // A square root is irrational unless m is 1, so its decimal value is shown too
+        Some(exact @ Exact::Radical {m, ..}) if *m != 1 => {
+            format!("{} ≈ {}", make_exact_string(exact), make_decimal_string(root))
+        },
+        Some(exact) => make_exact_string(exact),
+    };
+    let string = if root.multiplicity > 1 {format!("{} (multiplicity {})", string, root.multiplicity)} else {string};
+    match root.error {
+        Some(bound) if bound.is_finite() => format!("{} (error < {:.1e})", string, bound),
+        Some(_) => format!("{} (error unknown)", string),
+        None => string,
+    }
+}
+
+
+pub fn make_solution_string(solution: &Solution) -> String {
+    match solution {
+        Solution::NoSolution => "There is no solution.".to_string(),
+        Solution::AllReals => "The solution is an arbitrary real number.".to_string(),
+        Solution::Unsolvable => "The coefficients are too large to be computed, I can't solve.".to_string(),
+        Solution::Roots {degree, discriminant, roots} => {
+            let roots_string: Vec<String> = roots.iter().map(make_root_string).collect();
+            make_header_string(*degree, discriminant, roots) + roots_string.join("\n").as_str()
+        },
+    }
+}
+
+
+pub fn make_variable_solution_string(solution: &Solution, variable: char) -> String {
+    // X, in either case, is the special case: its roots stay bare values, the output the program has always had.
+    // Any other letter writes each root as variable = value so the letter is not lost
+    match solution {
+        Solution::Roots {degree, discriminant, roots} if !variable.eq_ignore_ascii_case(&'X') => {
+            let roots_string: Vec<String> = roots.iter().map(|root| format!("{} = {}", variable, make_root_string(root))).collect();
+            make_header_string(*degree, discriminant, roots) + roots_string.join("\n").as_str()
+        },
+        _ => make_solution_string(solution),
+    }
+}
+
+
+pub fn make_excluded_zero_string(variable: char) -> String {
+    format!("{v} = 0 is excluded, since the equation divides by {v}.", v = variable)
+}
+
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", make_solution_string(self))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::big_int::BigInt;
+    use crate::complex::Complex;
+
+    #[test]
+    fn radical_string_no_top() {
+        assert_eq!(radical_string(0, -3, 5, 4, false), "-3√5 / 4".to_string());
+    }
+
+    #[test]
+    fn radical_string_no_bottom() {
+        assert_eq!(radical_string(-1, 1, 2, 1, false), "-1 + √2".to_string());
+    }
+
+    #[test]
+    fn radical_string_imaginary() {
+        assert_eq!(radical_string(-1, -2, 3, 5, true), "(-1 - 2i√3) / 5".to_string());
+    }
+
+    #[test]
+    fn float_string_minus_zero() {
+        assert_eq!(float_string(-0.0), "0".to_string());
+    }
+
+    #[test]
+    fn make_root_string_rational() {
+        let root = Root {
+            value: Complex::new(-1.5, 0.0),
+            exact: Some(Exact::Rational(BigInt::from_i64(-3), BigInt::from_i64(2))),
+            error: None,
+            multiplicity: 1,
+        };
+        assert_eq!(make_root_string(&root), "-3 / 2".to_string());
+    }
+
+    #[test]
+    fn make_root_string_error() {
+        let root = Root {value: Complex::new(-0.0, -1.0), exact: None, error: Some(2.5e-15), multiplicity: 1};
+        assert_eq!(make_root_string(&root), "0 - 1i (error < 2.5e-15)".to_string());
+    }
+
+    #[test]
+    fn make_root_string_error_unknown() {
+        let root = Root {value: Complex::new(1.0, 0.0), exact: None, error: Some(f64::INFINITY), multiplicity: 1};
+        assert_eq!(make_root_string(&root), "1 (error unknown)".to_string());
+    }
+
+    #[test]
+    fn make_solution_string_no_solution() {
+        assert_eq!(make_solution_string(&Solution::NoSolution), "There is no solution.".to_string());
+    }
+
+    #[test]
+    fn make_variable_solution_string_roots() {
+        let solution = crate::solve(&crate::reduce("t^2 = 4").unwrap());
+        assert_eq!(make_variable_solution_string(&solution, 't'), "Discriminant is strictly positive, the two solutions are:\nt = 2\nt = -2".to_string());
+    }
+
+    #[test]
+    fn make_variable_solution_string_x() {
+        let solution = crate::solve(&crate::reduce("X^2 = 4").unwrap());
+        assert_eq!(make_variable_solution_string(&solution, 'X'), make_solution_string(&solution));
+        assert_eq!(make_variable_solution_string(&solution, 'x'), make_solution_string(&solution));
+    }
+
+    #[test]
+    fn roots_header_mixed() {
+        assert_eq!(roots_header(2, 2), "The two real solutions and the two complex solutions are:\n".to_string());
+    }
+}
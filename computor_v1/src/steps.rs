@@ -0,0 +1,227 @@
+use crate::Polynomial;
+use crate::parse_string::parse_string;
+use crate::elem_to_term::elem_to_term;
+use crate::solution::{Solution, make_terms_no_gaps};
+use crate::solution_string::{make_variable_solution_string, make_excluded_zero_string};
+use crate::term::{Term, Coefficient, Elem, Token};
+use crate::reduce_equation::reduce_equation;
+use crate::utility::{make_terms_string, make_reduced_form_string, hash_terms_to_sorted_vec};
+
+
+fn token_string(token: &Token, variable: char) -> String {
+    match &token.elem {
+        Elem::X => variable.to_string(),
+        Elem::Plus => "+".to_string(),
+        Elem::Minus => "-".to_string(),
+        Elem::Prod => "*".to_string(),
+        Elem::Div => "/".to_string(),
+        Elem::Power => "^".to_string(),
+        Elem::Equal => "=".to_string(),
+        Elem::LeftParen => "(".to_string(),
+        Elem::RightParen => ")".to_string(),
+        Elem::NumInt(n) => format!("{}", n),
+        Elem::NumBig(n) => format!("{}", n),
+        Elem::NumFloat(n) => format!("{}", n),
+        Elem::NumFraction(f) => format!("{}", Coefficient::NumFraction(*f)),
+        Elem::NumBigFraction(f) => format!("{}", Coefficient::NumBigFraction(f.clone())),
+        Elem::Constant(c) => c.name().to_string(),
+        Elem::Function(f) => f.name().to_string(),
+    }
+}
+
+
+fn value_string(coefficient: &Coefficient) -> String {
+    // Negative values and fractions are put in parentheses so they can be substituted into a formula
+    let string = format!("{}", coefficient);
+    if string.starts_with('-') || string.contains('/') {
+        format!("({})", string)
+    } else {
+        string
+    }
+}
+
+
+fn negated_string(coefficient: &Coefficient) -> String {
+    // -(-2) for -b with b = -2, and 0 rather than -0
+    if coefficient.is_zero() {
+        "0".to_string()
+    } else {
+        format!("-{}", value_string(coefficient))
+    }
+}
+
+
+fn degree_0_steps(terms: &[Term], x: char) -> String {
+    match terms.first() {
+        Some(term) if !term.coefficient.is_zero() => {
+            format!("No {x} remains and {} = 0 is false, so no {x} satisfies the equation.\n", term.coefficient, x = x)
+        },
+        _ => format!("No {x} remains and 0 = 0 is true, so every {x} satisfies the equation.\n", x = x),
+    }
+}
+
+
+fn degree_1_steps(terms: &[Term], x: char) -> String {
+    let terms = make_terms_no_gaps(terms, 1);
+    let c = &terms[0].coefficient;
+    let b = &terms[1].coefficient;
+    format!("The equation has the form b{x} + c = 0 with b = {}, c = {}.\n{x} = -c / b = {} / {}\n",
+        b, c, negated_string(c), value_string(b), x = x)
+}
+
+
+fn degree_2_steps(terms: &[Term], discriminant: &Coefficient, x: char) -> String {
+    let terms = make_terms_no_gaps(terms, 2);
+    let c = &terms[0].coefficient;
+    let b = &terms[1].coefficient;
+    let a = &terms[2].coefficient;
+    let mut string = format!("The equation has the form a{x}^2 + b{x} + c = 0 with a = {}, b = {}, c = {}.\n", a, b, c, x = x);
+    string += &format!("Δ = b^2 - 4ac = {}^2 - 4 * {} * {} = {}\n",
+        value_string(b), value_string(a), value_string(c), discriminant);
+    let bottom = format!("(2 * {})", value_string(a));
+    string += &if discriminant.is_zero() {
+        format!("Δ = 0, so there is one solution {} = -b / 2a = {} / {}\n", x, negated_string(b), bottom)
+    } else if discriminant.is_plus() {
+        format!("Δ > 0, so there are two solutions {} = (-b ± √Δ) / 2a = ({} ± √{}) / {}\n",
+            x, negated_string(b), value_string(discriminant), bottom)
+    } else {
+        format!("Δ < 0, so there are two complex solutions {} = (-b ± i√(-Δ)) / 2a = ({} ± i√{}) / {}\n",
+            x, negated_string(b), value_string(&discriminant.mul_minus()), bottom)
+    };
+    string
+}
+
+
+fn degree_3_steps(terms: &[Term], discriminant: &Coefficient, x: char) -> String {
+    let terms = make_terms_no_gaps(terms, 3);
+    format!("The equation has the form a{x}^3 + b{x}^2 + c{x} + d = 0 with a = {}, b = {}, c = {}, d = {}.\n\
+        Δ = b^2c^2 - 4ac^3 - 4b^3d - 27a^2d^2 + 18abcd = {}\n\
+        Rational roots are tried first, then Cardano's formula or the trigonometric method is used.\n",
+        terms[3].coefficient, terms[2].coefficient, terms[1].coefficient, terms[0].coefficient,
+        discriminant, x = x)
+}
+
+
+pub fn make_steps_string(equation: &str, polynomial: &Polynomial, solution: &Solution) -> String {
+    // The steps explain how the equation became the polynomial that was solved, and the solution found for it
+    let variable = polynomial.variable();
+    let mut string = String::new();
+    // The equation was reduced already, so it parses again
+    if let Ok(tokens) = parse_string(equation) {
+        let token_strings: Vec<String> = tokens.iter().map(|token| format!("[{}]", token_string(token, variable))).collect();
+        string += &format!("Tokens: {}\n", token_strings.join(" "));
+        if let Ok((left_terms, right_terms)) = elem_to_term(tokens) {
+            string += &format!("Left side terms: {}\n", make_terms_string(&left_terms, variable));
+            string += &format!("Right side terms: {}\n", make_terms_string(&right_terms, variable));
+            let mut moved = left_terms.clone();
+            moved.extend(right_terms.iter().map(|term| Term {coefficient: term.coefficient.mul_minus(), degree: term.degree}));
+            string += &format!("Move the right side terms to the left by changing their sign: {}\n", make_reduced_form_string(&moved, variable));
+            let combined = hash_terms_to_sorted_vec(reduce_equation(&left_terms, &right_terms));
+            string += &format!("Combine the terms of the same degree: {}\n", make_reduced_form_string(&combined, variable));
+            // The polynomial has its negative powers cleared already
+            if polynomial.terms() != combined.as_slice() {
+                let lowest = combined.iter().find(|term| !term.coefficient.is_zero()).map_or(0, |term| term.degree);
+                string += &format!("Multiply by {}^{} to clear the negative powers: {}\n", variable, -lowest, polynomial.reduced_form());
+            }
+        }
+    }
+    let degree = polynomial.degree();
+    string += &format!("Polynomial degree: {}\n", degree);
+
+    let terms = polynomial.terms();
+    let discriminant = match solution {
+        Solution::Roots {discriminant: Some(discriminant), ..} => Some(discriminant),
+        _ => None,
+    };
+    string += &match (degree, discriminant) {
+        (0, _) => degree_0_steps(terms, variable),
+        (1, _) => degree_1_steps(terms, variable),
+        (2, Some(discriminant)) => degree_2_steps(terms, discriminant, variable),
+        (3, Some(discriminant)) => degree_3_steps(terms, discriminant, variable),
+        (2 | 3, None) => "The coefficients are too large for the formula.\n".to_string(),
+        (4, _) => "The equation is of the fourth degree, rational roots are tried first, then Ferrari's method is used.\n".to_string(),
+        _ => "There is no formula above the fourth degree, so the rational roots are divided out and the others are approximated with the Durand-Kerner method.\n".to_string(),
+    };
+    string += &make_variable_solution_string(solution, variable);
+    if polynomial.excludes_zero() {
+        string += &format!("\n{}", make_excluded_zero_string(variable));
+    }
+    string
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reduce, solve};
+
+    fn steps(equation: &str) -> String {
+        let polynomial = reduce(equation).unwrap();
+        make_steps_string(equation, &polynomial, &solve(&polynomial))
+    }
+
+    #[test]
+    fn make_steps_string_degree_2() {
+        assert_eq!(steps("X^2 + 1 = 2X + 4"), [
+            "Tokens: [X] [^] [2] [+] [1] [=] [2] [X] [+] [4]",
+            "Left side terms: 1 * X^2 + 1 * X^0",
+            "Right side terms: 2 * X^1 + 4 * X^0",
+            "Move the right side terms to the left by changing their sign: 1 * X^2 + 1 * X^0 - 2 * X^1 - 4 * X^0 = 0",
+            "Combine the terms of the same degree: - 3 * X^0 - 2 * X^1 + 1 * X^2 = 0",
+            "Polynomial degree: 2",
+            "The equation has the form aX^2 + bX + c = 0 with a = 1, b = -2, c = -3.",
+            "Δ = b^2 - 4ac = (-2)^2 - 4 * 1 * (-3) = 16",
+            "Δ > 0, so there are two solutions X = (-b ± √Δ) / 2a = (-(-2) ± √16) / (2 * 1)",
+            "Discriminant is strictly positive, the two solutions are:",
+            "3",
+            "-1",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn make_steps_string_decimal() {
+        let string = steps("0.5X = 1");
+        assert!(string.contains("with b = 0.5, c = -1.\n"));
+        assert!(string.ends_with("X = -c / b = -(-1) / 0.5\nThe solution is:\n2"));
+    }
+
+    #[test]
+    fn make_steps_string_complex() {
+        let string = steps("5X^2 + 4X + 1 = 0");
+        assert!(string.contains("X = (-b ± i√(-Δ)) / 2a = (-4 ± i√4) / (2 * 5)\n"));
+    }
+
+    #[test]
+    fn make_steps_string_no_solution() {
+        let string = steps("1 = 2");
+        assert!(string.ends_with("No X remains and -1 = 0 is false, so no X satisfies the equation.\nThere is no solution."));
+    }
+
+    #[test]
+    fn make_steps_string_variable() {
+        let string = steps("2t = 1");
+        assert!(string.starts_with("Tokens: [2] [t] [=] [1]\nLeft side terms: 2 * t^1\n"));
+        assert!(string.ends_with("t = -c / b = -(-1) / 2\nThe solution is:\nt = 1 / 2"));
+    }
+
+    #[test]
+    fn make_steps_string_negative_degree() {
+        let string = steps("X + 1 / X = 2");
+        assert!(string.contains("Multiply by X^1 to clear the negative powers: 1 * X^0 - 2 * X^1 + 1 * X^2 = 0\nPolynomial degree: 2\n"));
+        assert!(string.ends_with("Discriminant is zero, the solution is:\n1 (multiplicity 2)\nX = 0 is excluded, since the equation divides by X."));
+    }
+
+    #[test]
+    fn make_steps_string_fraction() {
+        let string = steps("0.5X^2 + X/3 = 1");
+        assert!(string.contains("Δ = b^2 - 4ac = (1 / 3)^2 - 4 * 0.5 * (-1) = 19 / 9\n"));
+        assert!(string.contains("X = (-b ± √Δ) / 2a = (-(1 / 3) ± √(19 / 9)) / (2 * 0.5)\n"));
+    }
+
+    #[test]
+    fn make_steps_string_zero_b() {
+        let string = steps("X^2 = 2");
+        assert!(string.contains("X = (-b ± √Δ) / 2a = (0 ± √8) / (2 * 1)\n"));
+        assert!(steps("3X = 0").contains("X = -c / b = 0 / 3\n"));
+    }
+}
@@ -1,20 +1,70 @@
+use std::fmt;
+
+use crate::error::Span;
+use crate::fraction::Fraction;
+use crate::big_int::BigInt;
+use crate::big_fraction::BigFraction;
+use crate::math_utility::math_utility::{sqrt, cbrt, int_sqrt, PI, E};
+
+
+// The solver keeps one coefficient for every degree up to the highest
+pub const MAX_DEGREE: i64 = 1000;
+
+// Powers are expanded only while their exact coefficients stay below this size, as the work grows with its square
+pub const MAX_COEFFICIENT_BITS: usize = 4096;
+
+
 #[derive(Debug, PartialEq)]
 pub enum Elem {
     X,
     Plus,
     Minus,
     Prod,
+    Div,
     Power,
     Equal,
+    LeftParen,
+    RightParen,
     NumInt(i64),
+    NumBig(BigInt),
     NumFloat(f64),
+    NumFraction(Fraction),
+    NumBigFraction(BigFraction),
+    Constant(Constant),
+    Function(Function),
+}
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Constant {
+    Pi,
+    E,
+}
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Function {
+    Sqrt,
+    Cbrt,
+    Abs,
 }
 
 
+#[derive(Debug, PartialEq)]
+pub struct Token {
+    pub elem: Elem,
+    pub span: Span,
+}
+
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum Coefficient {
     NumInt(i64),
+    NumBig(BigInt),
     NumFloat(f64),
+    NumFraction(Fraction),
+    NumBigFraction(BigFraction),
 }
 
 
@@ -25,24 +75,90 @@ pub struct Term {
 }
 
 
+impl Token {
+    pub fn new(elem: Elem, start: usize, end: usize) -> Token {
+        Token {elem, span: Span::new(start, end)}
+    }
+}
+
+
 impl Coefficient {
+    pub fn from_fraction(fraction: Fraction) -> Coefficient {
+        if fraction.bottom() == 1 {
+            Coefficient::NumInt(fraction.top())
+        } else {
+            Coefficient::NumFraction(fraction)
+        }
+    }
+
+    pub fn from_big_int(n: BigInt) -> Coefficient {
+        match n.to_i64() {
+            Some(value) => Coefficient::NumInt(value),
+            None => Coefficient::NumBig(n),
+        }
+    }
+
+    pub fn from_big_fraction(fraction: BigFraction) -> Coefficient {
+        if fraction.bottom() == &BigInt::from_i64(1) {
+            return Coefficient::from_big_int(fraction.top().clone())
+        }
+        match fraction.to_fraction() {
+            Some(f) => Coefficient::NumFraction(f),
+            None => Coefficient::NumBigFraction(fraction),
+        }
+    }
+
+    pub fn to_big_int(&self) -> Option<BigInt> {
+        match self {
+            Coefficient::NumInt(n) => Some(BigInt::from_i64(*n)),
+            Coefficient::NumBig(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn to_fraction(&self) -> Option<Fraction> {
+        match self {
+            Coefficient::NumInt(n) => Fraction::safe_new(*n, 1),
+            Coefficient::NumFraction(f) => Some(*f),
+            Coefficient::NumFloat(n) => Fraction::from_float(*n),
+            Coefficient::NumBig(_) | Coefficient::NumBigFraction(_) => None,
+        }
+    }
+
+    pub fn to_big_fraction(&self) -> Option<BigFraction> {
+        // None for a float, the other coefficients are exact
+        match self {
+            Coefficient::NumInt(n) => Some(BigFraction::from_big_int(BigInt::from_i64(*n))),
+            Coefficient::NumBig(n) => Some(BigFraction::from_big_int(n.clone())),
+            Coefficient::NumFraction(f) => Some(BigFraction::from_fraction(f)),
+            Coefficient::NumBigFraction(f) => Some(f.clone()),
+            Coefficient::NumFloat(_) => None,
+        }
+    }
+
     pub fn add(&self, other: &Coefficient) -> Coefficient {
         match (self, other) {
             (Coefficient::NumInt(n1), Coefficient::NumInt(n2)) => {
                 match n1.checked_add(*n2) {
                     Some(n) => Coefficient::NumInt(n),
-                    None => Coefficient::NumFloat(*n1 as f64 + *n2 as f64),
+                    None => Coefficient::from_big_int(BigInt::from_i64(*n1).add(&BigInt::from_i64(*n2))),
                 }
             },
-            (Coefficient::NumInt(n1), Coefficient::NumFloat(n2)) => {
-                Coefficient::NumFloat(*n1 as f64 + n2)
+            (Coefficient::NumBig(_), Coefficient::NumInt(_) | Coefficient::NumBig(_))
+            | (Coefficient::NumInt(_), Coefficient::NumBig(_)) => {
+                Coefficient::from_big_int(self.to_big_int().unwrap().add(&other.to_big_int().unwrap()))
             },
-            (Coefficient::NumFloat(n1), Coefficient::NumInt(n2)) => {
-                Coefficient::NumFloat(n1 + *n2 as f64)
+            (Coefficient::NumFloat(_), _) | (_, Coefficient::NumFloat(_)) => {
+                Coefficient::NumFloat(self.to_float() + other.to_float())
+            },
+            _ => {
+                let sum = self.to_fraction().zip(other.to_fraction())
+                    .and_then(|(f1, f2)| f1.checked_add(&f2));
+                match sum {
+                    Some(f) => Coefficient::from_fraction(f),
+                    None => Coefficient::from_big_fraction(self.to_big_fraction().unwrap().add(&other.to_big_fraction().unwrap())),
+                }
             },
-            (Coefficient::NumFloat(n1), Coefficient::NumFloat(n2)) => {
-                Coefficient::NumFloat(n1 + n2)
-            }
         }
     }
 
@@ -51,32 +167,70 @@ impl Coefficient {
             (Coefficient::NumInt(n1), Coefficient::NumInt(n2)) => {
                 match n1.checked_mul(*n2) {
                     Some(n) => Coefficient::NumInt(n),
-                    None => Coefficient::NumFloat(*n1 as f64 * *n2 as f64),
+                    None => Coefficient::NumBig(BigInt::from_i64(*n1).mul(&BigInt::from_i64(*n2))),
                 }
             },
-            (Coefficient::NumInt(n1), Coefficient::NumFloat(n2)) => {
-                Coefficient::NumFloat(*n1 as f64 * n2)
+            (Coefficient::NumBig(_), Coefficient::NumInt(_) | Coefficient::NumBig(_))
+            | (Coefficient::NumInt(_), Coefficient::NumBig(_)) => {
+                Coefficient::from_big_int(self.to_big_int().unwrap().mul(&other.to_big_int().unwrap()))
+            },
+            (Coefficient::NumFloat(_), _) | (_, Coefficient::NumFloat(_)) => {
+                Coefficient::NumFloat(self.to_float() * other.to_float())
             },
-            (Coefficient::NumFloat(n1), Coefficient::NumInt(n2)) => {
-                Coefficient::NumFloat(n1 * *n2 as f64)
+            _ => {
+                let product = self.to_fraction().zip(other.to_fraction())
+                    .and_then(|(f1, f2)| f1.checked_mul(&f2));
+                match product {
+                    Some(f) => Coefficient::from_fraction(f),
+                    None => Coefficient::from_big_fraction(self.to_big_fraction().unwrap().mul(&other.to_big_fraction().unwrap())),
+                }
             },
-            (Coefficient::NumFloat(n1), Coefficient::NumFloat(n2)) => {
-                Coefficient::NumFloat(n1 * n2)
-            }
         }
     }
 
+    pub fn div(&self, other: &Coefficient) -> Option<Coefficient> {
+        // None when dividing by zero, the quotient stays exact unless a float is involved
+        if other.is_zero() {
+            return None
+        }
+        Some(match (self, other) {
+            (Coefficient::NumFloat(_), _) | (_, Coefficient::NumFloat(_)) => {
+                Coefficient::NumFloat(self.to_float() / other.to_float())
+            },
+            _ => {
+                let quotient = self.to_fraction().zip(other.to_fraction())
+                    .and_then(|(f1, f2)| f1.checked_div(&f2));
+                match quotient {
+                    Some(f) => Coefficient::from_fraction(f),
+                    None => Coefficient::from_big_fraction(self.to_big_fraction()?.div(&other.to_big_fraction()?)?),
+                }
+            },
+        })
+    }
+
     pub fn mul_minus(&self) -> Coefficient {
         match self {
             Coefficient::NumInt(n) => {
                 match n.checked_mul(-1) {
                     Some(value) => Coefficient::NumInt(value),
-                    None => Coefficient::NumFloat(*n as f64 * -1_f64),
+                    None => Coefficient::NumBig(BigInt::from_i64(*n).neg()),
                 }
             },
+            Coefficient::NumBig(n) => {
+                Coefficient::from_big_int(n.neg())
+            },
             Coefficient::NumFloat(n) => {
                 Coefficient::NumFloat(n * -1_f64)
             },
+            Coefficient::NumFraction(f) => {
+                match f.checked_neg() {
+                    Some(value) => Coefficient::NumFraction(value),
+                    None => Coefficient::from_big_fraction(BigFraction::from_fraction(f).neg()),
+                }
+            },
+            Coefficient::NumBigFraction(f) => {
+                Coefficient::from_big_fraction(f.neg())
+            },
         }
     }
 
@@ -85,9 +239,18 @@ impl Coefficient {
             Coefficient::NumInt(n) => {
                 *n == 0
             },
+            Coefficient::NumBig(n) => {
+                n.is_zero()
+            },
             Coefficient::NumFloat(n) => {
                 *n == 0.0
             },
+            Coefficient::NumFraction(f) => {
+                f.top() == 0
+            },
+            Coefficient::NumBigFraction(f) => {
+                f.top().is_zero()
+            },
         }
     }
 
@@ -96,9 +259,18 @@ impl Coefficient {
             Coefficient::NumInt(n) => {
                 *n as f64
             },
+            Coefficient::NumBig(n) => {
+                n.to_float()
+            },
             Coefficient::NumFloat(n) => {
                 *n
             },
+            Coefficient::NumFraction(f) => {
+                f.to_float()
+            },
+            Coefficient::NumBigFraction(f) => {
+                f.to_float()
+            },
         }
     }
 
@@ -107,9 +279,119 @@ impl Coefficient {
             Coefficient::NumInt(n) => {
                 *n >= 0
             },
+            Coefficient::NumBig(n) => {
+                !n.is_negative()
+            },
             Coefficient::NumFloat(n) => {
                 *n >= 0.0
             },
+            Coefficient::NumFraction(f) => {
+                f.top() >= 0
+            },
+            Coefficient::NumBigFraction(f) => {
+                !f.top().is_negative()
+            },
+        }
+    }
+}
+
+
+fn exact_sqrt(n: i64) -> Option<i64> {
+    let root = int_sqrt(n)?;
+    if root * root == n {Some(root)} else {None}
+}
+
+
+fn exact_cbrt(n: i64) -> Option<i64> {
+    let root = cbrt(n as f64).round() as i64;
+    if root.checked_mul(root)?.checked_mul(root)? == n {Some(root)} else {None}
+}
+
+
+fn root_coefficient(value: &Coefficient, exact_root: fn(i64) -> Option<i64>, float_root: fn(f64) -> f64) -> Coefficient {
+    // The root of a perfect power stays exact
+    let exact = match value {
+        Coefficient::NumInt(n) => exact_root(*n).map(Coefficient::NumInt),
+        Coefficient::NumFraction(f) => exact_root(f.top()).zip(exact_root(f.bottom()))
+            .and_then(|(top, bottom)| Fraction::safe_new(top, bottom))
+            .map(Coefficient::from_fraction),
+        _ => None,
+    };
+    exact.unwrap_or_else(|| Coefficient::NumFloat(float_root(value.to_float())))
+}
+
+
+impl Constant {
+    pub fn from_name(name: &str) -> Option<Constant> {
+        match name {
+            "pi" | "π" => Some(Constant::Pi),
+            "e" => Some(Constant::E),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Constant::Pi => "pi",
+            Constant::E => "e",
+        }
+    }
+
+    pub fn value(&self) -> Coefficient {
+        match self {
+            Constant::Pi => Coefficient::NumFloat(PI),
+            Constant::E => Coefficient::NumFloat(E),
+        }
+    }
+}
+
+
+impl Function {
+    pub fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "sqrt" => Some(Function::Sqrt),
+            "cbrt" => Some(Function::Cbrt),
+            "abs" => Some(Function::Abs),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Sqrt => "sqrt",
+            Function::Cbrt => "cbrt",
+            Function::Abs => "abs",
+        }
+    }
+
+    pub fn apply(&self, value: &Coefficient) -> Option<Coefficient> {
+        // None for the square root of a negative number
+        match self {
+            Function::Sqrt if !value.is_plus() => None,
+            Function::Sqrt => Some(root_coefficient(value, exact_sqrt, sqrt)),
+            Function::Cbrt => Some(root_coefficient(value, exact_cbrt, cbrt)),
+            Function::Abs if value.is_plus() => Some(value.clone()),
+            Function::Abs => Some(value.mul_minus()),
+        }
+    }
+}
+
+
+impl fmt::Display for Coefficient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Coefficient::NumInt(n) => write!(f, "{}", n),
+            Coefficient::NumBig(n) => write!(f, "{}", n),
+            Coefficient::NumFloat(n) => write!(f, "{}", n),
+            // Decimals typed by the user are shown as decimals
+            Coefficient::NumFraction(fraction) => match fraction.to_decimal_string() {
+                Some(s) => write!(f, "{}", s),
+                None => write!(f, "{}", fraction),
+            },
+            Coefficient::NumBigFraction(fraction) => match fraction.to_decimal_string() {
+                Some(s) => write!(f, "{}", s),
+                None => write!(f, "{}", fraction),
+            },
         }
     }
 }
@@ -151,7 +433,7 @@ mod tests {
     fn coefficient_add_error_int_int_overflow() {
         let lhs = Coefficient::NumInt(9223372036854775807);
         let rhs = Coefficient::NumInt(1);
-        assert_eq!(lhs.add(&rhs), Coefficient::NumFloat(9223372036854775808_f64));
+        assert_eq!(lhs.add(&rhs), Coefficient::NumBig(BigInt::parse("9223372036854775808").unwrap()));
     }
 
     #[test]
@@ -186,7 +468,7 @@ mod tests {
     fn coefficient_mul_error_int_int_overflow() {
         let lhs = Coefficient::NumInt(9223372036854775807);
         let rhs = Coefficient::NumInt(2);
-        assert_eq!(lhs.mul(&rhs), Coefficient::NumFloat(9223372036854775807_f64 * 2.0));
+        assert_eq!(lhs.mul(&rhs), Coefficient::NumBig(BigInt::parse("18446744073709551614").unwrap()));
     }
 
     #[test]
@@ -196,9 +478,9 @@ mod tests {
     }
     
     #[test]
-    fn coefficient_mul_minus_int_to_float() {
+    fn coefficient_mul_minus_int_to_big() {
         let value = Coefficient::NumInt(-9223372036854775808);
-        assert_eq!(value.mul_minus(), Coefficient::NumFloat(9223372036854775808_f64));
+        assert_eq!(value.mul_minus(), Coefficient::NumBig(BigInt::parse("9223372036854775808").unwrap()));
     }
 
     #[test]
@@ -210,25 +492,25 @@ mod tests {
     #[test]
     fn is_zero_int_zero() {
         let value = Coefficient::NumInt(0);
-        assert_eq!(value.is_zero(), true);
+        assert!(value.is_zero());
     }
 
     #[test]
     fn is_zero_int_one() {
         let value = Coefficient::NumInt(1);
-        assert_eq!(value.is_zero(), false);
+        assert!(!value.is_zero());
     }
 
     #[test]
     fn is_zero_float_zerp() {
         let value = Coefficient::NumFloat(0.0);
-        assert_eq!(value.is_zero(), true);
+        assert!(value.is_zero());
     }
 
     #[test]
     fn is_zero_float_one() {
         let value = Coefficient::NumFloat(1.0);
-        assert_eq!(value.is_zero(), false);
+        assert!(!value.is_zero());
     }
 
     #[test]
@@ -246,24 +528,171 @@ mod tests {
     #[test]
     fn is_plus_int_plus() {
         let value = Coefficient::NumInt(1);
-        assert_eq!(value.is_plus(), true);
+        assert!(value.is_plus());
     }
 
     #[test]
     fn is_plus_int_minus() {
         let value = Coefficient::NumInt(-1);
-        assert_eq!(value.is_plus(), false);
+        assert!(!value.is_plus());
     }
 
     #[test]
     fn is_plus_float_plus() {
         let value = Coefficient::NumFloat(1.0);
-        assert_eq!(value.is_plus(), true);
+        assert!(value.is_plus());
     }
 
     #[test]
     fn is_plus_float_minus() {
         let value = Coefficient::NumFloat(-1.0);
-        assert_eq!(value.is_plus(), false);
+        assert!(!value.is_plus());
+    }
+
+    #[test]
+    fn coefficient_add_fraction_fraction() {
+        let lhs = Coefficient::NumFraction(Fraction::safe_new(1, 10).unwrap());
+        let rhs = Coefficient::NumFraction(Fraction::safe_new(2, 10).unwrap().make_irreducible_fraction());
+        assert_eq!(lhs.add(&rhs), Coefficient::NumFraction(Fraction::safe_new(3, 10).unwrap()));
+    }
+
+    #[test]
+    fn coefficient_add_fraction_to_int() {
+        let lhs = Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap());
+        let rhs = Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap());
+        assert_eq!(lhs.add(&rhs), Coefficient::NumInt(1));
+    }
+
+    #[test]
+    fn coefficient_add_int_fraction() {
+        let lhs = Coefficient::NumInt(1);
+        let rhs = Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap());
+        assert_eq!(lhs.add(&rhs), Coefficient::NumFraction(Fraction::safe_new(3, 2).unwrap()));
+    }
+
+    #[test]
+    fn coefficient_add_fraction_float() {
+        let lhs = Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap());
+        let rhs = Coefficient::NumFloat(1.0);
+        assert_eq!(lhs.add(&rhs), Coefficient::NumFloat(1.5));
+    }
+
+    #[test]
+    fn coefficient_add_fraction_overflow_to_big_fraction() {
+        let lhs = Coefficient::NumFraction(Fraction::safe_new(1, i64::MAX).unwrap());
+        let rhs = Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap());
+        let sum = BigFraction::new(BigInt::parse("9223372036854775809").unwrap(), BigInt::parse("18446744073709551614").unwrap());
+        assert_eq!(lhs.add(&rhs), Coefficient::NumBigFraction(sum.unwrap()));
+    }
+
+    #[test]
+    fn coefficient_add_big_fraction_back_to_fraction() {
+        let big = BigFraction::new(BigInt::from_i64(1), BigInt::parse("36893488147419103232").unwrap()).unwrap();
+        let lhs = Coefficient::NumBigFraction(big.clone());
+        let rhs = Coefficient::NumBigFraction(big).mul_minus().add(&Coefficient::NumFraction(Fraction::safe_new(1, 3).unwrap()));
+        assert_eq!(lhs.add(&rhs), Coefficient::NumFraction(Fraction::safe_new(1, 3).unwrap()));
+    }
+
+    #[test]
+    fn coefficient_mul_fraction_int() {
+        let lhs = Coefficient::NumFraction(Fraction::safe_new(3, 10).unwrap());
+        let rhs = Coefficient::NumInt(10);
+        assert_eq!(lhs.mul(&rhs), Coefficient::NumInt(3));
+    }
+
+    #[test]
+    fn coefficient_mul_minus_fraction() {
+        let value = Coefficient::NumFraction(Fraction::safe_new(1, 2).unwrap());
+        assert_eq!(value.mul_minus(), Coefficient::NumFraction(Fraction::safe_new(-1, 2).unwrap()));
+    }
+
+    #[test]
+    fn is_plus_fraction_minus() {
+        let value = Coefficient::NumFraction(Fraction::safe_new(-1, 2).unwrap());
+        assert!(!value.is_plus());
+    }
+
+    #[test]
+    fn coefficient_add_big_back_to_int() {
+        let lhs = Coefficient::NumBig(BigInt::parse("9223372036854775808").unwrap());
+        let rhs = Coefficient::NumInt(-1);
+        assert_eq!(lhs.add(&rhs), Coefficient::NumInt(i64::MAX));
+    }
+
+    #[test]
+    fn coefficient_mul_big_float() {
+        let lhs = Coefficient::NumBig(BigInt::parse("9223372036854775808").unwrap());
+        let rhs = Coefficient::NumFloat(0.5);
+        assert_eq!(lhs.mul(&rhs), Coefficient::NumFloat(4611686018427387904.0));
+    }
+
+    #[test]
+    fn coefficient_mul_minus_big_to_int() {
+        let value = Coefficient::NumBig(BigInt::parse("9223372036854775808").unwrap());
+        assert_eq!(value.mul_minus(), Coefficient::NumInt(i64::MIN));
+    }
+
+    #[test]
+    fn coefficient_div_int_exact() {
+        let value = Coefficient::NumInt(3).div(&Coefficient::NumInt(-6));
+        assert_eq!(value, Some(Coefficient::NumFraction(Fraction::safe_new(-1, 2).unwrap())));
+    }
+
+    #[test]
+    fn coefficient_div_big_int() {
+        let value = Coefficient::NumBig(BigInt::parse("18446744073709551616").unwrap()).div(&Coefficient::NumInt(4));
+        assert_eq!(value, Some(Coefficient::NumInt(4611686018427387904)));
+    }
+
+    #[test]
+    fn coefficient_div_big_inexact() {
+        let value = Coefficient::NumBig(BigInt::parse("18446744073709551617").unwrap()).div(&Coefficient::NumInt(4));
+        let quotient = BigFraction::new(BigInt::parse("18446744073709551617").unwrap(), BigInt::from_i64(4)).unwrap();
+        assert_eq!(value, Some(Coefficient::NumBigFraction(quotient)));
+    }
+
+    #[test]
+    fn coefficient_div_zero() {
+        assert_eq!(Coefficient::NumFloat(1.5).div(&Coefficient::NumInt(0)), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn to_fraction_float() {
+        let value = Coefficient::NumFloat(-2.5);
+        assert_eq!(value.to_fraction(), Fraction::safe_new(-5, 2));
+    }
+
+    #[test]
+    fn to_fraction_big() {
+        let value = Coefficient::NumBig(BigInt::parse("9223372036854775808").unwrap());
+        assert_eq!(value.to_fraction(), None);
+    }
+
+    #[test]
+    fn coefficient_display_fraction() {
+        let decimal = Coefficient::NumFraction(Fraction::safe_new(-93, 10).unwrap());
+        let fraction = Coefficient::NumFraction(Fraction::safe_new(1, 3).unwrap());
+        assert_eq!(format!("{} {}", decimal, fraction), "-9.3 1 / 3".to_string());
+    }
+
+    #[test]
+    fn function_sqrt_exact() {
+        let value = Coefficient::NumFraction(Fraction::safe_new(9, 4).unwrap());
+        assert_eq!(Function::Sqrt.apply(&value), Some(Coefficient::NumFraction(Fraction::safe_new(3, 2).unwrap())));
+    }
+
+    #[test]
+    fn function_sqrt_float() {
+        assert_eq!(Function::Sqrt.apply(&Coefficient::NumInt(2)), Some(Coefficient::NumFloat(sqrt(2.0))));
+    }
+
+    #[test]
+    fn function_sqrt_negative() {
+        assert_eq!(Function::Sqrt.apply(&Coefficient::NumInt(-4)), None);
+    }
+
+    #[test]
+    fn function_cbrt_negative() {
+        assert_eq!(Function::Cbrt.apply(&Coefficient::NumInt(-27)), Some(Coefficient::NumInt(-3)));
+    }
+}
@@ -1,36 +1,59 @@
 use std::collections::HashMap;
 
 use crate::term::{Term, Coefficient};
+use crate::big_int::BigInt;
+use crate::error::ParseError;
 
 
 pub fn hash_terms_to_sorted_vec(terms: HashMap<i64, Term>) -> Vec<Term> {
     let mut vec: Vec<Term> = terms.into_values().collect();
-    vec.sort_by(|a, b| a.degree.cmp(&b.degree));
+    vec.sort_by_key(|a| a.degree);
     vec
 }
 
 
-pub fn make_reduced_form_string(terms: &Vec<Term>) -> String {
+pub fn make_terms_string(terms: &[Term], variable: char) -> String {
     let mut string = String::new();
     for term in terms {
-        string += match term.coefficient {
+        string += match &term.coefficient {
             Coefficient::NumInt(n) => {
-                if n < 0 {
-                    format!("- {} * X^{} ", -n, term.degree)
+                if *n < 0 {
+                    format!("- {} * {}^{} ", n.unsigned_abs(), variable, term.degree)
                 } else if string.is_empty() {
-                    format!("{} * X^{} ", n, term.degree)
+                    format!("{} * {}^{} ", n, variable, term.degree)
                 } else {
-                    format!("+ {} * X^{} ", n, term.degree)
+                    format!("+ {} * {}^{} ", n, variable, term.degree)
                 }
             },
             
+            Coefficient::NumBig(n) => {
+                if n.is_negative() {
+                    format!("- {} * {}^{} ", n.neg(), variable, term.degree)
+                } else if string.is_empty() {
+                    format!("{} * {}^{} ", n, variable, term.degree)
+                } else {
+                    format!("+ {} * {}^{} ", n, variable, term.degree)
+                }
+            },
+
             Coefficient::NumFloat(n) => {
-                if n < 0.0 {
-                    format!("- {} * X^{} ", -n, term.degree)
+                if *n < 0.0 {
+                    format!("- {} * {}^{} ", -n, variable, term.degree)
+                } else if string.is_empty() {
+                    format!("{} * {}^{} ", n, variable, term.degree)
+                } else {
+                    format!("+ {} * {}^{} ", n, variable, term.degree)
+                }
+            },
+
+            Coefficient::NumFraction(_) | Coefficient::NumBigFraction(_) => {
+                let value = format!("{}", term.coefficient);
+                if let Some(v) = value.strip_prefix('-') {
+                    format!("- {} * {}^{} ", v, variable, term.degree)
                 } else if string.is_empty() {
-                    format!("{} * X^{} ", n, term.degree)
+                    format!("{} * {}^{} ", value, variable, term.degree)
                 } else {
-                    format!("+ {} * X^{} ", n, term.degree)
+                    format!("+ {} * {}^{} ", value, variable, term.degree)
                 }
             },
         }.as_str();
@@ -38,12 +61,71 @@ pub fn make_reduced_form_string(terms: &Vec<Term>) -> String {
     if string.is_empty() {
         string += "0 ";
     }
-    string += "= 0";
+    string.pop();
     string
 }
 
 
-pub fn evaluate_degree_of_terms(terms: &Vec<Term>) -> i64 {
+pub fn make_reduced_form_string(terms: &[Term], variable: char) -> String {
+    format!("{} = 0", make_terms_string(terms, variable))
+}
+
+
+fn superscript(n: i64) -> String {
+    format!("{}", n).chars().map(|c| match c {
+        '-' => '⁻',
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        _ => '⁹',
+    }).collect()
+}
+
+
+pub fn make_natural_form_string(terms: &[Term], is_superscript: bool, variable: char) -> String {
+    // Highest degree first, without zero terms, unit coefficients, X^0 and ^1
+    let mut string = String::new();
+    for term in terms.iter().rev() {
+        if term.coefficient.is_zero() {
+            continue
+        }
+        let value = format!("{}", term.coefficient);
+        let (is_minus, mut value) = match value.strip_prefix('-') {
+            Some(v) => (true, v.to_string()),
+            None => (false, value),
+        };
+        if term.degree != 0 && value == "1" {
+            value.clear();
+        } else if term.degree != 0 && value.contains(' ') {
+            value = format!("({})", value);
+        }
+        let power = match (term.degree, is_superscript) {
+            (0, _) => String::new(),
+            (1, _) => variable.to_string(),
+            (n, true) => format!("{}{}", variable, superscript(n)),
+            (n, false) => format!("{}^{}", variable, n),
+        };
+        string += &match (string.is_empty(), is_minus) {
+            (true, true) => format!("-{}{}", value, power),
+            (true, false) => format!("{}{}", value, power),
+            (false, true) => format!(" - {}{}", value, power),
+            (false, false) => format!(" + {}{}", value, power),
+        };
+    }
+    if string.is_empty() {
+        string += "0";
+    }
+    string + " = 0"
+}
+
+
+pub fn evaluate_degree_of_terms(terms: &[Term]) -> i64 {
     let mut degree = 0;
     for term in terms {
         if !term.coefficient.is_zero() {
@@ -54,6 +136,28 @@ pub fn evaluate_degree_of_terms(terms: &Vec<Term>) -> i64 {
 }
 
 
+pub fn make_error_string(equation: &str, error: &ParseError) -> String {
+    // The span is in bytes, the caret is placed in characters
+    let start = error.span.start.min(equation.len());
+    let end = error.span.end.min(equation.len());
+    let column = equation[..start].chars().count();
+    let width = equation[start..end].chars().count().max(1);
+    format!("{}\n{}{}\n{}", equation, " ".repeat(column), "^".repeat(width), error)
+}
+
+
+pub fn lcm_of_bottoms(terms: &[Term]) -> Option<BigInt> {
+    // The smallest number that makes every coefficient an integer, None when one is a float
+    let mut lcm = BigInt::from_i64(1);
+    for term in terms {
+        let bottom = term.coefficient.to_big_fraction()?.bottom().clone();
+        let (quotient, _) = lcm.div_rem(&lcm.gcd(&bottom))?;
+        lcm = quotient.mul(&bottom);
+    }
+    Some(lcm)
+}
+
+
 pub fn is_int_value(v: f64) -> bool {
     let int_v = v as i64;
     v - int_v as f64 == 0.0
@@ -95,25 +199,25 @@ mod tests {
     #[test]
     fn make_reduced_form_string_empty() {
         let vec = Vec::new();
-        assert_eq!(make_reduced_form_string(&vec), "0 = 0".to_string());
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "0 = 0".to_string());
     }
 
     #[test]
     fn make_reduced_form_string_zero_int() {
         let vec = vec![Term { coefficient: Coefficient::NumInt(0), degree: (1) }];
-        assert_eq!(make_reduced_form_string(&vec), "0 * X^1 = 0".to_string());
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "0 * X^1 = 0".to_string());
     }
 
     #[test]
     fn make_reduced_form_string_zero_float() {
         let vec = vec![Term { coefficient: Coefficient::NumFloat(0.0), degree: (1) }];
-        assert_eq!(make_reduced_form_string(&vec), "0 * X^1 = 0".to_string());
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "0 * X^1 = 0".to_string());
     }
 
     #[test]
     fn make_reduced_form_string_first_plus() {
         let vec = vec![Term { coefficient: Coefficient::NumInt(1), degree: (1) }];
-        assert_eq!(make_reduced_form_string(&vec), "1 * X^1 = 0".to_string());
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "1 * X^1 = 0".to_string());
     }
 
     #[test]
@@ -123,7 +227,7 @@ mod tests {
             Term { coefficient: Coefficient::NumInt(-2), degree: (1) },
             Term { coefficient: Coefficient::NumInt(1), degree: (2) },
         ];
-        assert_eq!(make_reduced_form_string(&vec), "- 1 * X^0 - 2 * X^1 + 1 * X^2 = 0".to_string());
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "- 1 * X^0 - 2 * X^1 + 1 * X^2 = 0".to_string());
     }
 
     #[test]
@@ -133,7 +237,64 @@ mod tests {
             Term { coefficient: Coefficient::NumFloat(-2.2), degree: (1) },
             Term { coefficient: Coefficient::NumFloat(1.2), degree: (2) },
         ];
-        assert_eq!(make_reduced_form_string(&vec), "- 1.2 * X^0 - 2.2 * X^1 + 1.2 * X^2 = 0".to_string());
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "- 1.2 * X^0 - 2.2 * X^1 + 1.2 * X^2 = 0".to_string());
+    }
+
+    #[test]
+    fn make_reduced_form_string_fraction() {
+        use crate::fraction::Fraction;
+        let vec = vec![
+            Term { coefficient: Coefficient::NumFraction(Fraction::safe_new(-93, 10).unwrap()), degree: (0) },
+            Term { coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 3).unwrap()), degree: (1) },
+        ];
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "- 9.3 * X^0 + 1 / 3 * X^1 = 0".to_string());
+    }
+
+    #[test]
+    fn make_reduced_form_string_big() {
+        use crate::big_int::BigInt;
+        let vec = vec![
+            Term { coefficient: Coefficient::NumInt(i64::MIN), degree: (0) },
+            Term { coefficient: Coefficient::NumBig(BigInt::parse("-99999999999999999999").unwrap()), degree: (1) },
+        ];
+        assert_eq!(make_reduced_form_string(&vec, 'X'), "- 9223372036854775808 * X^0 - 99999999999999999999 * X^1 = 0".to_string());
+    }
+
+    #[test]
+    fn make_natural_form_string_normal() {
+        let vec = vec![
+            Term { coefficient: Coefficient::NumInt(1), degree: 0 },
+            Term { coefficient: Coefficient::NumInt(0), degree: 1 },
+            Term { coefficient: Coefficient::NumInt(-1), degree: 2 },
+            Term { coefficient: Coefficient::NumInt(-2), degree: 3 },
+        ];
+        assert_eq!(make_natural_form_string(&vec, false, 'X'), "-2X^3 - X^2 + 1 = 0".to_string());
+    }
+
+    #[test]
+    fn make_natural_form_string_superscript() {
+        use crate::fraction::Fraction;
+        let vec = vec![
+            Term { coefficient: Coefficient::NumFraction(Fraction::safe_new(1, 3).unwrap()), degree: 1 },
+            Term { coefficient: Coefficient::NumFraction(Fraction::safe_new(-93, 10).unwrap()), degree: 12 },
+        ];
+        assert_eq!(make_natural_form_string(&vec, true, 'X'), "-9.3X¹² + (1 / 3)X = 0".to_string());
+    }
+
+    #[test]
+    fn make_natural_form_string_zero() {
+        let vec = vec![Term { coefficient: Coefficient::NumInt(0), degree: 1 }];
+        assert_eq!(make_natural_form_string(&vec, true, 'X'), "0 = 0".to_string());
+    }
+
+    #[test]
+    fn make_natural_form_string_variable() {
+        let vec = vec![
+            Term { coefficient: Coefficient::NumInt(-4), degree: 0 },
+            Term { coefficient: Coefficient::NumInt(1), degree: 1 },
+            Term { coefficient: Coefficient::NumInt(1), degree: 2 },
+        ];
+        assert_eq!(make_natural_form_string(&vec, false, 't'), "t^2 + t - 4 = 0".to_string());
     }
 
     #[test]
@@ -160,11 +321,32 @@ mod tests {
 
     #[test]
     fn is_int_value_int() {
-        assert_eq!(is_int_value(1.0), true);
+        assert!(is_int_value(1.0));
     }
 
     #[test]
     fn is_int_value_float() {
-        assert_eq!(is_int_value(1.1), false);
+        assert!(!is_int_value(1.1));
+    }
+
+    #[test]
+    fn make_error_string_one_char() {
+        use crate::error::{ErrorKind, Span};
+        let error = ParseError::new(ErrorKind::UnsupportedChar('a'), Span::new(3, 4));
+        assert_eq!(make_error_string("123a", &error), "123a\n   ^\nUnsupported characters: a".to_string());
+    }
+
+    #[test]
+    fn make_error_string_end_of_input() {
+        use crate::error::{ErrorKind, Span};
+        let error = ParseError::new(ErrorKind::MissingEqual, Span::new(5, 6));
+        assert_eq!(make_error_string("X + 1", &error), "X + 1\n     ^\nThere was no equal".to_string());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn make_error_string_multibyte() {
+        use crate::error::{ErrorKind, Span};
+        let error = ParseError::new(ErrorKind::UnsupportedChar('²'), Span::new(4, 6));
+        assert_eq!(make_error_string("é X² = 0", &error), "é X² = 0\n   ^\nUnsupported characters: ²".to_string());
+    }
+}